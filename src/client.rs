@@ -1,19 +1,252 @@
 use super::data::*;
+use super::enums::{
+    AdminLevel, EntryMethod, EventType, Feeling, GroupType, How, InvitationStatus, PrivacySetting,
+    UnitType, Where, WritingType,
+};
 use super::error::Error;
 use super::kind::NanoKind;
-
-use std::collections::HashMap;
-use std::sync::Arc;
-
+#[cfg(feature = "raw_value")]
+use super::utils::RawAttributes;
+use super::utils::{update_view, write_view};
+
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::future::Future;
+use std::sync::{Arc, RwLock as StdRwLock};
+use std::time::{Duration, Instant};
+
+use chrono::{DateTime, NaiveDate, SecondsFormat, TimeZone, Utc};
+use futures_util::{future, stream, Stream, StreamExt, TryStreamExt};
+use reqwest::header::HeaderMap;
 use reqwest::{Client, Method, StatusCode};
-use serde::de::DeserializeOwned;
-use serde::Serialize;
-use tokio::sync::RwLock;
+use serde::de::{DeserializeOwned, IgnoredAny};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use tokio::sync::{Mutex, OnceCell, RwLock};
 use tracing::{error, trace};
 
 #[cfg(test)]
 mod tests;
 
+/// The number of items requested per page when paginating
+const PAGE_SIZE: u64 = 25;
+
+/// The maximum number of requests made at once when fanning out over many items
+const CONCURRENCY: usize = 8;
+
+/// How many times a request is retried when the connection to the API fails
+const CONNECTION_RETRIES: u32 = 2;
+
+/// The linked items included when getting the dashboard, spanning several levels of relations
+const DASHBOARD_INCLUDES: &str = "projects.project-challenges.challenge,notifications,groups";
+
+/// The linked items included when getting a region by its slug: its members, and their users
+const REGION_INCLUDES: &str = "group-users.user";
+
+/// How far back the buddy activity feed goes, in days
+const ACTIVITY_DAYS: i64 = 7;
+
+/// How far ahead [`NanoClient::challenges_ending_soon`] looks at most, in days
+const MAX_CHALLENGE_DAYS: i64 = 36_525;
+
+fn add_page(data: &mut Vec<(String, String)>, page: u64) {
+    data.push(("page[number]".to_string(), page.to_string()));
+    data.push(("page[size]".to_string(), PAGE_SIZE.to_string()));
+}
+
+/// Turn a function fetching a numbered page into a stream of pages, starting at a given page,
+/// each along with the number of the page after it.
+///
+/// Stops after the last page as told by the pagination links, or once the record count has been
+/// reached, if the API provides either. Otherwise stops after the first page which isn't full.
+/// Items already seen on an earlier page are left out, and a page with nothing new is taken as
+/// the last, so that an endpoint which ignores pagination isn't fetched forever.
+fn paginate_pages<'a, D, F, Fut>(
+    start: Option<u64>,
+    fetch: F,
+) -> impl Stream<Item = Result<(Vec<D>, Option<u64>), Error>> + 'a
+where
+    D: ObjectInfo + 'a,
+    F: Fn(u64) -> Fut + 'a,
+    Fut: Future<Output = Result<CollectionResponse<D>, Error>> + 'a,
+{
+    // Items before the starting page aren't seen, so are assumed to fill their pages
+    let skipped = start.map_or(0, |page| page.saturating_sub(1) * PAGE_SIZE);
+
+    stream::try_unfold(
+        (start, HashSet::new()),
+        move |(page, mut seen): (Option<u64>, HashSet<(NanoKind, u64)>)| {
+            let next = page.map(|page| (page, fetch(page)));
+            async move {
+                let Some((page, fut)) = next else {
+                    return Ok::<_, Error>(None);
+                };
+
+                let mut resp = fut.await?;
+                let full = resp.data.len() as u64 >= PAGE_SIZE;
+                resp.data
+                    .retain(|item| seen.insert((item.kind(), item.id())));
+
+                let more = if resp.data.is_empty() {
+                    false
+                } else if let Some(more) = resp.has_next_page() {
+                    more
+                } else if let Some(count) = resp.record_count() {
+                    skipped + (seen.len() as u64) < count
+                } else {
+                    full
+                };
+                let next = more.then_some(page + 1);
+
+                Ok(Some(((resp.data, next), (next, seen))))
+            }
+        },
+    )
+}
+
+/// Turn a function fetching a numbered page into a stream of all the items of all the pages,
+/// stopping after the first page which isn't full
+fn paginate<'a, D, F, Fut>(fetch: F) -> impl Stream<Item = Result<D, Error>> + 'a
+where
+    D: ObjectInfo + 'a,
+    F: Fn(u64) -> Fut + 'a,
+    Fut: Future<Output = Result<CollectionResponse<D>, Error>> + 'a,
+{
+    paginate_pages(Some(1), fetch)
+        .map_ok(|(items, _)| stream::iter(items.into_iter().map(Ok)))
+        .try_flatten()
+}
+
+/// Fail if a response body of the given size, in bytes, is larger than the limit
+fn check_response_size(size: u64, limit: usize) -> Result<(), Error> {
+    if size > limit as u64 {
+        Err(Error::ResponseTooLarge { limit })
+    } else {
+        Ok(())
+    }
+}
+
+/// Read a response body chunk by chunk, stopping as soon as it grows past the limit
+#[cfg(not(target_arch = "wasm32"))]
+async fn read_body_capped(mut resp: reqwest::Response, limit: usize) -> Result<Vec<u8>, Error> {
+    let mut body = Vec::new();
+    while let Some(chunk) = resp.chunk().await? {
+        check_response_size((body.len() + chunk.len()) as u64, limit)?;
+        body.extend_from_slice(&chunk);
+    }
+
+    Ok(body)
+}
+
+/// Read a response body, failing if it's larger than the limit. The browser doesn't give access
+/// to the body as it arrives, so this can only be checked once it's all been read.
+#[cfg(target_arch = "wasm32")]
+async fn read_body_capped(resp: reqwest::Response, limit: usize) -> Result<Vec<u8>, Error> {
+    let body = resp.bytes().await?;
+    check_response_size(body.len() as u64, limit)?;
+    Ok(body.to_vec())
+}
+
+/// Pick the winner badge for a kind of event among badges. Of the active winner badges, those for
+/// Camp NaNoWriMo are told apart by the word "Camp" in their title, and the first in the order of
+/// the catalog is picked. There is none for custom events.
+fn pick_winner_badge(
+    badges: impl IntoIterator<Item = BadgeObject>,
+    event_type: EventType,
+) -> Option<BadgeObject> {
+    let camp = match event_type {
+        EventType::NanoWrimo => false,
+        EventType::CampNano => true,
+        _ => return None,
+    };
+
+    badges
+        .into_iter()
+        .filter(|badge| {
+            let attributes = &badge.attributes;
+            let is_camp = attributes
+                .title
+                .split(|c: char| !c.is_alphanumeric())
+                .any(|word| word.eq_ignore_ascii_case("camp"));
+
+            attributes.winner && attributes.active && is_camp == camp
+        })
+        .min_by_key(|badge| (badge.attributes.list_order, badge.id))
+}
+
+/// Whether a failed request is worth making again. Requests which couldn't connect never reached
+/// the API, but a request which timed out may have been processed, so it's only retried when
+/// repeating it is harmless. Connection errors can't be told apart in the browser, where only
+/// timeouts are retried.
+fn is_reconnectable(err: &reqwest::Error, method: &Method) -> bool {
+    #[cfg(not(target_arch = "wasm32"))]
+    if err.is_connect() {
+        return true;
+    }
+
+    err.is_timeout() && method.is_idempotent()
+}
+
+/// Build the relationships of an object being written, tying it to single other objects
+fn relate_to(refs: &[(NanoKind, u64)]) -> Option<RelationInfo> {
+    Some(RelationInfo {
+        relations: Default::default(),
+        included: refs
+            .iter()
+            .map(|&(kind, id)| {
+                (
+                    kind,
+                    vec![ObjectRef {
+                        id,
+                        kind,
+                        extra: Default::default(),
+                    }],
+                )
+            })
+            .collect(),
+    })
+}
+
+/// Build the request body creating a project session, tied to a project and one of its
+/// challenges
+fn project_session_body(
+    project_id: u64,
+    project_challenge_id: u64,
+    attributes: ProjectSessionData,
+) -> Result<serde_json::Value, serde_json::Error> {
+    let data = ItemResponse {
+        data: Object::ProjectSession(ProjectSessionObject {
+            id: 0,
+            links: None,
+            attributes,
+            relationships: relate_to(&[
+                (NanoKind::Project, project_id),
+                (NanoKind::ProjectChallenge, project_challenge_id),
+            ]),
+        }),
+        included: None,
+        post_info: None,
+        extra: Default::default(),
+    };
+
+    write_view(&data)
+}
+
+/// The attributes of a project session which happened at a known time, dated on the day it
+/// started in the time zone of its start
+fn timed_session_data<Tz: TimeZone>(
+    count: i64,
+    start: DateTime<Tz>,
+    end: DateTime<Tz>,
+) -> ProjectSessionData {
+    ProjectSessionData {
+        count,
+        session_date: Some(start.date_naive()),
+        start: Some(start.with_timezone(&Utc)),
+        end: Some(end.with_timezone(&Utc)),
+        ..Default::default()
+    }
+}
+
 fn add_included(data: &mut Vec<(String, String)>, include: &[NanoKind]) {
     if !include.is_empty() {
         data.push((
@@ -27,6 +260,29 @@ fn add_included(data: &mut Vec<(String, String)>, include: &[NanoKind]) {
     }
 }
 
+/// How the authorization token is sent to the Nano API
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum AuthScheme {
+    /// The token is sent as-is in the `Authorization` header, like the Nano website does
+    #[default]
+    Raw,
+    /// The token is sent as `Bearer {token}` in the `Authorization` header
+    Bearer,
+}
+
+type ResponseFn = dyn Fn(&str, Duration, usize) + Send + Sync;
+
+/// A callback invoked after every response, see [`NanoClient::with_on_response`]
+#[derive(Clone)]
+struct ResponseHook(Arc<ResponseFn>);
+
+impl std::fmt::Debug for ResponseHook {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("ResponseHook")
+    }
+}
+
 #[derive(Clone, Debug)]
 struct Creds {
     username: String,
@@ -39,6 +295,14 @@ pub struct NanoClient {
     client: Client,
     creds: Option<Arc<Creds>>,
     token: Arc<RwLock<Option<String>>>,
+    relogin: Arc<Mutex<()>>,
+    auth_scheme: AuthScheme,
+    default_includes: Arc<StdRwLock<HashMap<NanoKind, Vec<NanoKind>>>>,
+    badges: Arc<OnceCell<HashMap<u64, BadgeObject>>>,
+    on_response: Option<ResponseHook>,
+    language: Option<String>,
+    default_headers: HeaderMap,
+    max_response_size: Option<usize>,
 }
 
 impl NanoClient {
@@ -52,6 +316,14 @@ impl NanoClient {
                 password: pass.into(),
             })),
             token: Default::default(),
+            relogin: Default::default(),
+            auth_scheme: Default::default(),
+            default_includes: Default::default(),
+            badges: Default::default(),
+            on_response: None,
+            language: None,
+            default_headers: HeaderMap::new(),
+            max_response_size: None,
         }
     }
 
@@ -61,6 +333,14 @@ impl NanoClient {
             client: Client::new(),
             creds: None,
             token: Default::default(),
+            relogin: Default::default(),
+            auth_scheme: Default::default(),
+            default_includes: Default::default(),
+            badges: Default::default(),
+            on_response: None,
+            language: None,
+            default_headers: HeaderMap::new(),
+            max_response_size: None,
         }
     }
 
@@ -71,6 +351,89 @@ impl NanoClient {
         Ok(client)
     }
 
+    /// Use a specific reqwest [`Client`] for all requests made by this client, in order to
+    /// configure connection reuse, timeouts, proxies, and such.
+    ///
+    /// For batch jobs making many requests in a row, such as during the November traffic spike,
+    /// a larger idle pool kept around for longer avoids reconnecting for every request. HTTP/2 is
+    /// negotiated automatically when the server supports it, which multiplexes concurrent
+    /// requests over a single connection:
+    ///
+    /// ```no_run
+    /// # use std::time::Duration;
+    /// # use nanowrimo::NanoClient;
+    /// let http = reqwest::Client::builder()
+    ///     .pool_max_idle_per_host(32)
+    ///     .pool_idle_timeout(Duration::from_secs(90))
+    ///     .tcp_keepalive(Duration::from_secs(60))
+    ///     .build()
+    ///     .expect("Couldn't build reqwest client");
+    ///
+    /// let client = NanoClient::new_anon().with_client(http);
+    /// ```
+    pub fn with_client(mut self, client: Client) -> NanoClient {
+        self.client = client;
+        self
+    }
+
+    /// Use a specific scheme to send the authorization token with. The default is
+    /// [`AuthScheme::Raw`], which is what the Nano website itself uses.
+    pub fn with_auth_scheme(mut self, scheme: AuthScheme) -> NanoClient {
+        self.auth_scheme = scheme;
+        self
+    }
+
+    /// Ask for content in a specific language, such as `fr` or `pt-BR`, by sending it in the
+    /// `Accept-Language` header of all requests. Pages and posts with a localized variant are then
+    /// returned in that language, others in the default one.
+    pub fn with_language(mut self, lang: &str) -> NanoClient {
+        self.language = Some(lang.into());
+        self
+    }
+
+    /// Send some headers with every request, such as a proxy authorization token or a tracing
+    /// correlation ID. These are sent in addition to the headers this client sets itself, like
+    /// `Authorization`, so shouldn't include those.
+    pub fn with_default_headers(mut self, headers: HeaderMap) -> NanoClient {
+        self.default_headers = headers;
+        self
+    }
+
+    /// Refuse responses with a body larger than a number of bytes, failing with
+    /// [`Error::ResponseTooLarge`] instead of reading them whole into memory. There is no limit
+    /// by default.
+    pub fn with_max_response_size(mut self, bytes: usize) -> NanoClient {
+        self.max_response_size = Some(bytes);
+        self
+    }
+
+    /// Read the body of a response, up to the maximum response size if one is set
+    async fn read_body(&self, resp: reqwest::Response) -> Result<Vec<u8>, Error> {
+        let Some(limit) = self.max_response_size else {
+            return Ok(resp.bytes().await?.to_vec());
+        };
+
+        if let Some(length) = resp.content_length() {
+            check_response_size(length, limit)?;
+        }
+
+        read_body_capped(resp, limit).await
+    }
+
+    /// Call a function after every response from the Nano API, with the path requested, the time
+    /// taken from sending the request to having read the whole response, and the size of the
+    /// response body in bytes. This is meant for collecting metrics.
+    ///
+    /// Not available on wasm32, where the standard clock can't be read.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn with_on_response<F>(mut self, hook: F) -> NanoClient
+    where
+        F: Fn(&str, Duration, usize) + Send + Sync + 'static,
+    {
+        self.on_response = Some(ResponseHook(Arc::new(hook)));
+        self
+    }
+
     async fn make_request<T, U>(&self, path: &str, method: Method, data: &T) -> Result<U, Error>
     where
         T: Serialize + ?Sized + std::fmt::Debug,
@@ -88,10 +451,18 @@ impl NanoClient {
 
         let mut req = self
             .client
-            .request(method, format!("{}{}", NanoClient::BASE_URL, path));
+            .request(method, format!("{}{}", NanoClient::BASE_URL, path))
+            .headers(self.default_headers.clone());
 
         if let Some(token) = self.token.read().await.as_deref() {
-            req = req.header("Authorization", token)
+            req = match self.auth_scheme {
+                AuthScheme::Raw => req.header("Authorization", token),
+                AuthScheme::Bearer => req.bearer_auth(token),
+            }
+        }
+
+        if let Some(lang) = &self.language {
+            req = req.header(reqwest::header::ACCEPT_LANGUAGE, lang);
         }
 
         if let Some(query) = query {
@@ -109,9 +480,22 @@ impl NanoClient {
             req = req.json(json);
         }
 
+        let started = self.on_response.as_ref().map(|_| Instant::now());
+        let report = |size: usize| {
+            if let (Some(hook), Some(started)) = (&self.on_response, started) {
+                (hook.0)(path, started.elapsed(), size);
+            }
+        };
+
         let resp = req.send().await?;
 
         let status = resp.status();
+        if matches!(
+            status,
+            StatusCode::INTERNAL_SERVER_ERROR | StatusCode::NOT_FOUND
+        ) {
+            report(resp.content_length().unwrap_or_default() as usize);
+        }
 
         match status {
             StatusCode::INTERNAL_SERVER_ERROR => {
@@ -126,51 +510,126 @@ impl NanoClient {
             _ => (),
         }
 
-        let nano_resp = resp.text().await?;
+        let body = self.read_body(resp).await?;
+        report(body.len());
+
+        let nano_resp = std::str::from_utf8(&body).map_err(|err| {
+            error!(%err, "nanowrimo.org response isn't valid UTF-8");
+            Error::bad_encoding(&body, err)
+        })?;
+        // Some commands respond with nothing at all, which is treated as null
+        let nano_resp = if nano_resp.trim().is_empty() {
+            "null"
+        } else {
+            nano_resp
+        };
         trace!(?nano_resp, "response from nanowrimo.org");
 
-        let nano_val: serde_json::Value = serde_json::from_str(&nano_resp).unwrap_or_default();
-        if nano_val.as_object().map_or(false, |obj| {
-            obj.contains_key("error") || obj.contains_key("errors")
-        }) {
+        let nano_val: serde_json::Value = serde_json::from_str(nano_resp).unwrap_or_default();
+        if nano_val
+            .as_object()
+            .is_some_and(|obj| obj.contains_key("error") || obj.contains_key("errors"))
+        {
             // parse the error(s)
             let nano_error: NanoError = serde_json::from_value(nano_val)?;
             return match nano_error {
-                NanoError::SimpleError { error } => Err(Error::SimpleNanoError(status, error)),
-                NanoError::ErrorList { errors } => Err(Error::NanoErrors(errors)),
+                NanoError::SimpleError { error, .. } => Err(Error::SimpleNanoError(status, error)),
+                NanoError::ErrorList { errors, .. } => Err(Error::NanoErrors(errors)),
             };
         }
 
-        let jd = &mut serde_json::Deserializer::from_str(&nano_resp);
+        if status == StatusCode::FORBIDDEN {
+            return Err(Error::SimpleNanoError(status, "Forbidden".to_string()));
+        }
+
+        let jd = &mut serde_json::Deserializer::from_str(nano_resp);
         let nano_resp = serde_path_to_error::deserialize(jd).map_err(|err| {
-            let path = err.path().to_string();
+            let path = err.path().clone();
             let err = err.into_inner();
             error!(%path, %err, raw=%nano_val, "error parsing nanowrimo.org response as json");
-            Error::ResponseDecoding { path, err }
+            Error::response_decoding(&path, &nano_val, err)
         })?;
         trace!(?nano_resp, "response from nanowrimo.org");
 
         Ok(nano_resp)
     }
 
+    /// Make a request, retrying it a bounded number of times if the connection failed or timed
+    /// out. Timeouts are only retried for idempotent methods, as the request may have been
+    /// processed already.
+    async fn reconnecting_request<T, U>(
+        &self,
+        path: &str,
+        method: Method,
+        data: &T,
+    ) -> Result<U, Error>
+    where
+        T: Serialize + ?Sized + std::fmt::Debug,
+        U: DeserializeOwned + std::fmt::Debug,
+    {
+        let mut attempt = 0;
+        loop {
+            match self.make_request(path, method.clone(), data).await {
+                Err(Error::ReqwestError(err))
+                    if attempt < CONNECTION_RETRIES && is_reconnectable(&err, &method) =>
+                {
+                    attempt += 1;
+                    trace!(?path, attempt, %err, "retrying request to nanowrimo.org");
+                }
+                res => return res,
+            }
+        }
+    }
+
     async fn retry_request<T, U>(&self, path: &str, method: Method, data: &T) -> Result<U, Error>
     where
         T: Serialize + ?Sized + std::fmt::Debug,
         U: DeserializeOwned + std::fmt::Debug,
     {
-        let res = self.make_request(path, method.clone(), data).await;
+        let used = self.token.read().await.clone();
+        let res = self.reconnecting_request(path, method.clone(), data).await;
 
         match res {
             Err(Error::SimpleNanoError(code, _))
                 if code == StatusCode::UNAUTHORIZED && self.is_logged_in().await =>
             {
-                self.login().await?;
-                self.make_request(path, method, data).await
+                self.relogin(used.as_deref()).await?;
+                self.reconnecting_request(path, method, data).await
             }
             _ => res,
         }
     }
 
+    /// Set the linked items to always include when getting items of a given kind, when no
+    /// includes are explicitly requested. This applies to this client and all its clones. Setting
+    /// an empty list removes the defaults for that kind.
+    pub fn set_default_includes(&self, kind: NanoKind, include: &[NanoKind]) {
+        let mut defaults = self
+            .default_includes
+            .write()
+            .unwrap_or_else(|err| err.into_inner());
+
+        if include.is_empty() {
+            defaults.remove(&kind);
+        } else {
+            defaults.insert(kind, include.to_vec());
+        }
+    }
+
+    /// The includes to request for a kind: the explicit ones if there are any, or the defaults
+    fn includes_for(&self, kind: NanoKind, include: &[NanoKind]) -> Vec<NanoKind> {
+        if !include.is_empty() {
+            return include.to_vec();
+        }
+
+        self.default_includes
+            .read()
+            .unwrap_or_else(|err| err.into_inner())
+            .get(&kind)
+            .cloned()
+            .unwrap_or_default()
+    }
+
     /// Check whether this client is currently logged in
     pub async fn is_logged_in(&self) -> bool {
         self.token.read().await.is_some()
@@ -195,13 +654,37 @@ impl NanoClient {
         Ok(())
     }
 
-    /// Log out this client, without checking if it's logged in
+    /// Log in again after a token was rejected. When many requests are rejected at once, only
+    /// the first one to get here logs in: the others wait for it, and then find the token
+    /// already replaced.
+    async fn relogin(&self, rejected: Option<&str>) -> Result<(), Error> {
+        let _guard = self.relogin.lock().await;
+
+        if self.token.read().await.as_deref() != rejected {
+            return Ok(());
+        }
+
+        self.login().await
+    }
+
+    /// Log out this client, without checking if it's logged in. The token is forgotten even if
+    /// the server fails to log it out, so the client is anonymous afterwards either way.
     pub async fn logout(&self) -> Result<(), Error> {
-        self.make_request::<_, ()>("users/logout", Method::POST, &())
-            .await?;
+        let res = self
+            .make_request::<_, ()>("users/logout", Method::POST, &())
+            .await;
         self.token.write().await.take();
 
-        Ok(())
+        res
+    }
+
+    /// Log out this client if it's logged in, and do nothing otherwise
+    pub async fn logout_if_logged_in(&self) -> Result<(), Error> {
+        if !self.is_logged_in().await {
+            return Ok(());
+        }
+
+        self.logout().await
     }
 
     // Commands
@@ -211,12 +694,67 @@ impl NanoClient {
         self.retry_request("fundometer", Method::GET, &()).await
     }
 
+    /// Get headline numbers about Nano as a whole: how many people take part, and the
+    /// fundraising state
+    pub async fn site_stats(&self) -> Result<SiteStats, Error> {
+        let path = format!("{}/everyone", NanoKind::Group.api_name());
+        let everyone = self.retry_request::<_, ItemResponse<GroupObject>>(&path, Method::GET, &());
+
+        let (everyone, fundometer) = future::try_join(everyone, self.fundometer()).await?;
+
+        Ok(SiteStats {
+            participants: everyone.data.attributes.member_count,
+            fundometer,
+        })
+    }
+
+    /// Check that the Nano API is reachable and, if this client is logged in, that its token is
+    /// still accepted. Returns false if the token was rejected. The response isn't decoded, so
+    /// this is much cheaper than fetching the current user.
+    pub async fn ping(&self) -> Result<bool, Error> {
+        let path = if self.is_logged_in().await {
+            "users/current"
+        } else {
+            "fundometer"
+        };
+
+        match self
+            .make_request::<_, IgnoredAny>(path, Method::GET, &())
+            .await
+        {
+            Ok(_) => Ok(true),
+            Err(Error::SimpleNanoError(code, _)) if code == StatusCode::UNAUTHORIZED => Ok(false),
+            Err(err) => Err(err),
+        }
+    }
+
     /// Search for users by username
     pub async fn search(&self, name: &str) -> Result<CollectionResponse<UserObject>, Error> {
         self.retry_request("search", Method::GET, &[("q", name)])
             .await
     }
 
+    /// Search for users by username, getting a specific page of the results. Pages start at 1.
+    pub async fn search_paginated(
+        &self,
+        name: &str,
+        page: u64,
+    ) -> Result<CollectionResponse<UserObject>, Error> {
+        let mut data = vec![("q".to_string(), name.to_string())];
+
+        add_page(&mut data, page);
+
+        self.retry_request("search", Method::GET, &data).await
+    }
+
+    /// Search for users by username, streaming every match across all pages of the results
+    pub fn search_all<'a>(
+        &'a self,
+        name: &'a str,
+    ) -> impl Stream<Item = Result<UserObject, Error>> + 'a {
+        paginate(move |page| self.search_paginated(name, page))
+    }
+
     /// Get a random sponsor offer
     pub async fn random_offer(&self) -> Result<ItemResponse<PostObject>, Error> {
         self.retry_request("random_offer", Method::GET, &()).await
@@ -232,6 +770,22 @@ impl NanoClient {
         self.retry_request("offers", Method::GET, &()).await
     }
 
+    /// Get a list of the sponsor offers which are currently active, that is published and not
+    /// yet expired
+    pub async fn active_offers(&self) -> Result<Vec<ItemResponse<PostObject>>, Error> {
+        let today = Utc::now().date_naive();
+
+        Ok(self
+            .offers()
+            .await?
+            .into_iter()
+            .filter(|offer| {
+                let post = &offer.data.attributes;
+                post.published && post.expires_at.is_none_or(|expires| expires >= today)
+            })
+            .collect())
+    }
+
     /// Get the currently logged in user, with included linked items
     pub async fn current_user_include(
         &self,
@@ -250,6 +804,151 @@ impl NanoClient {
         self.current_user_include(&[]).await
     }
 
+    /// Get the currently logged in user, with their projects, project challenges, groups, and
+    /// notifications included. This is what an app usually needs on startup. (See
+    /// [`Self::dashboard`] for the same, sorted out by kind)
+    pub async fn current_user_full(&self) -> Result<ItemResponse<UserObject>, Error> {
+        self.current_user_include(&[
+            NanoKind::Project,
+            NanoKind::ProjectChallenge,
+            NanoKind::Group,
+            NanoKind::Notification,
+        ])
+        .await
+    }
+
+    /// Get the current user along with their projects, the challenges of those, their
+    /// notifications, and their groups, all in a single request
+    pub async fn dashboard(&self) -> Result<DashboardData, Error> {
+        if !self.is_logged_in().await {
+            return Err(Error::NoCredentials);
+        };
+
+        let resp = self
+            .retry_request::<_, ItemResponse<UserObject>>(
+                "users/current",
+                Method::GET,
+                &[("include", DASHBOARD_INCLUDES)],
+            )
+            .await?;
+
+        let mut dashboard = DashboardData {
+            user: resp.data,
+            projects: Vec::new(),
+            project_challenges: Vec::new(),
+            challenges: Vec::new(),
+            notifications: Vec::new(),
+            groups: Vec::new(),
+        };
+
+        for obj in resp.included.unwrap_or_default() {
+            match obj {
+                Object::Project(project) => dashboard.projects.push(project),
+                Object::ProjectChallenge(project_challenge) => {
+                    dashboard.project_challenges.push(project_challenge)
+                }
+                Object::Challenge(challenge) => dashboard.challenges.push(challenge),
+                Object::Notification(notification) => dashboard.notifications.push(notification),
+                Object::Group(group) => dashboard.groups.push(group),
+                _ => (),
+            }
+        }
+
+        Ok(dashboard)
+    }
+
+    /// Get all the settings of the current user, separate from the rest of their data. Pair
+    /// with [`UserSettings::diff`] to find which settings changed after editing them.
+    pub async fn settings_snapshot(&self) -> Result<UserSettings, Error> {
+        if !self.is_logged_in().await {
+            return Err(Error::NoCredentials);
+        };
+
+        let user = self.current_user().await?;
+
+        UserSettings::from_user(&user.data.attributes).ok_or_else(|| {
+            Error::BadJSON(serde::de::Error::custom(
+                "the current user was returned without their settings",
+            ))
+        })
+    }
+
+    /// Get whether the current user is reminded to write, by email and by notification
+    pub async fn writing_reminders(&self) -> Result<WritingReminders, Error> {
+        let settings = self.settings_snapshot().await?;
+
+        Ok(WritingReminders {
+            email: settings.email.writing_reminders,
+            notification: settings.notifications.writing_reminders,
+        })
+    }
+
+    /// Change whether the current user is reminded to write, by email and by notification,
+    /// leaving their other settings untouched
+    pub async fn set_writing_reminders(&self, reminders: WritingReminders) -> Result<(), Error> {
+        if !self.is_logged_in().await {
+            return Err(Error::NoCredentials);
+        };
+
+        let user_id = self.current_user().await?.data.id;
+        self.update_attributes::<UserObject>(
+            NanoKind::User,
+            user_id,
+            serde_json::to_value(reminders)?,
+        )
+        .await?;
+
+        Ok(())
+    }
+
+    /// Get which notifications the current user receives on the site
+    pub async fn get_notification_prefs(&self) -> Result<NotificationSettings, Error> {
+        Ok(self.settings_snapshot().await?.notifications)
+    }
+
+    /// Change which notifications the current user receives on the site, leaving their other
+    /// settings untouched
+    pub async fn set_notification_prefs(&self, prefs: NotificationSettings) -> Result<(), Error> {
+        if !self.is_logged_in().await {
+            return Err(Error::NoCredentials);
+        };
+
+        let user_id = self.current_user().await?.data.id;
+        self.update_attributes::<UserObject>(NanoKind::User, user_id, serde_json::to_value(prefs)?)
+            .await?;
+
+        Ok(())
+    }
+
+    /// Get which emails the current user receives
+    pub async fn get_email_prefs(&self) -> Result<EmailSettings, Error> {
+        Ok(self.settings_snapshot().await?.email)
+    }
+
+    /// Change which emails the current user receives, leaving their other settings untouched
+    pub async fn set_email_prefs(&self, prefs: EmailSettings) -> Result<(), Error> {
+        if !self.is_logged_in().await {
+            return Err(Error::NoCredentials);
+        };
+
+        let user_id = self.current_user().await?.data.id;
+        self.update_attributes::<UserObject>(NanoKind::User, user_id, serde_json::to_value(prefs)?)
+            .await?;
+
+        Ok(())
+    }
+
+    /// Check whether the currently logged in user is an admin
+    pub async fn is_admin(&self) -> Result<bool, Error> {
+        if !self.is_logged_in().await {
+            return Err(Error::NoCredentials);
+        };
+
+        let user = self.current_user().await?;
+
+        Ok(user.data.attributes.admin_level == AdminLevel::Admin)
+    }
+
     /// Get info about a specific set of pages. Known valid values include:
     ///
     /// - `"what-is-camp-nanowrimo"`
@@ -266,16 +965,76 @@ impl NanoClient {
     /// - `"brought-to-you-by"`
     ///
     /// If you know of other valid values, please open an issue with the values to add to this list!
-    pub async fn pages(&self, page: &str) -> Result<ItemResponse<PageObject>, Error> {
+    pub async fn pages(&self, page: &Slug) -> Result<ItemResponse<PageObject>, Error> {
         self.retry_request(&format!("pages/{}", page), Method::GET, &())
             .await
     }
 
+    /// Get the pep talks from the pep talks page, split into individual talks (see
+    /// [`PageData::pep_talks`])
+    pub async fn pep_talks(&self) -> Result<Vec<PepTalk>, Error> {
+        let page = self.pages(&Slug::try_from("pep-talks")?).await?;
+        Ok(page.data.attributes.pep_talks())
+    }
+
     /// Get the list of notifications for the current user
     pub async fn notifications(&self) -> Result<CollectionResponse<NotificationObject>, Error> {
         self.retry_request("notifications", Method::GET, &()).await
     }
 
+    /// Get the object a notification is about, such as the badge that was awarded or the
+    /// project that was updated, based on its action. Returns `None` if the notification doesn't
+    /// refer to a specific object.
+    pub async fn notification_target(
+        &self,
+        notif: &NotificationObject,
+    ) -> Result<Option<Object>, Error> {
+        let Some(id) = notif.attributes.action_id else {
+            return Ok(None);
+        };
+
+        let kind = notif.attributes.action_type.target_kind();
+
+        self.get_id::<Object>(kind, id)
+            .await
+            .map(|item| Some(item.data))
+    }
+
+    /// Mark a notification as viewed, so it doesn't show as new anymore
+    pub async fn mark_notification_viewed(&self, id: u64) -> Result<(), Error> {
+        self.update_attributes::<NotificationObject>(
+            NanoKind::Notification,
+            id,
+            json!({ "last-viewed-at": Utc::now() }),
+        )
+        .await?;
+
+        Ok(())
+    }
+
+    /// Mark many notifications as viewed at once. The requests are made concurrently, and if any
+    /// of them fail, the others still go through: the error lists which ones failed, by their
+    /// index in `ids`.
+    pub async fn dismiss_notifications(&self, ids: &[u64]) -> Result<(), Error> {
+        let errors = stream::iter(ids.iter().enumerate())
+            .map(|(index, &id)| async move {
+                self.mark_notification_viewed(id)
+                    .await
+                    .err()
+                    .map(|err| (index, err))
+            })
+            .buffer_unordered(CONCURRENCY)
+            .filter_map(future::ready)
+            .collect::<Vec<_>>()
+            .await;
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(Error::Batch(errors))
+        }
+    }
+
     /// Get a set of all the challenges this user has access to (Possibly all they can make
     /// projects in)
     pub async fn available_challenges(&self) -> Result<CollectionResponse<ChallengeObject>, Error> {
@@ -298,43 +1057,1045 @@ impl NanoClient {
         .await
     }
 
-    // Type queries
-
-    /// Get all accessible items of a specific kind, with included linked items and filtering to
-    /// certain related IDs.
-    ///
-    /// 'includes' will add more items in the response as part of an 'includes' list,
-    /// so one request can get more items
-    ///
-    /// 'filter' will filter certain types of objects by IDs of other objects related to them.
-    ///
-    /// **Warning**: Many filter combinations are invalid, and the rules are not currently fully
-    /// understood.
-    pub async fn get_all_include_filtered<D: ObjectInfo + DeserializeOwned>(
+    /// Get the daily aggregates of all the currently running project challenges of a user,
+    /// indexed by the ID of their project challenge. The aggregates of each project challenge
+    /// are fetched concurrently.
+    pub async fn all_daily_aggregates(
         &self,
-        ty: NanoKind,
-        include: &[NanoKind],
-        filter: &[(&str, u64)],
-    ) -> Result<CollectionResponse<D>, Error> {
-        let mut data = Vec::new();
+        user_id: u64,
+    ) -> Result<HashMap<u64, Vec<DailyAggregateObject>>, Error> {
+        let today = Utc::now().date_naive();
+
+        let active = self
+            .stream_all::<ProjectChallengeObject>(
+                NanoKind::ProjectChallenge,
+                &[("user_id", user_id)],
+            )
+            .try_collect::<Vec<_>>()
+            .await?
+            .into_iter()
+            .filter(|project_challenge| project_challenge.attributes.is_running_on(today))
+            .map(|project_challenge| project_challenge.id);
+
+        stream::iter(active.map(|id| async move {
+            let aggregates = self.daily_aggregates(id).await?;
+            Ok::<_, Error>((id, aggregates.data))
+        }))
+        .buffer_unordered(CONCURRENCY)
+        .try_collect()
+        .await
+    }
 
-        for i in filter {
-            data.push((format!("filter[{}]", i.0), i.1.to_string()))
-        }
+    /// Get the words written on a project each day, across all the challenges it was part of,
+    /// in chronological order. Days with no writing are left out.
+    pub async fn project_timeline(&self, project_id: u64) -> Result<Vec<(NaiveDate, u64)>, Error> {
+        let project_challenges = self
+            .stream_all::<ProjectChallengeObject>(
+                NanoKind::ProjectChallenge,
+                &[("project_id", project_id)],
+            )
+            .try_collect::<Vec<_>>()
+            .await?;
 
-        add_included(&mut data, include);
+        let aggregates = stream::iter(
+            project_challenges
+                .iter()
+                .map(|project_challenge| self.daily_aggregates(project_challenge.id)),
+        )
+        .buffer_unordered(CONCURRENCY)
+        .try_collect::<Vec<_>>()
+        .await?;
+
+        let mut timeline = BTreeMap::new();
+        for aggregate in aggregates
+            .into_iter()
+            .flat_map(|aggregates| aggregates.data)
+        {
+            if aggregate.attributes.unit_type == UnitType::Words {
+                *timeline.entry(aggregate.attributes.day).or_insert(0) +=
+                    aggregate.attributes.count;
+            }
+        }
 
-        self.retry_request(ty.api_name(), Method::GET, &data).await
+        Ok(timeline.into_iter().collect())
     }
 
-    /// Get all accessible items of a specific kind, with filtering to certain related IDs
-    /// (See [`Self::get_all_include_filtered`])
-    pub async fn get_all_filtered<D: ObjectInfo + DeserializeOwned>(
+    /// Get the current user's running project challenges which end within a number of days and
+    /// haven't been won yet, soonest ending first
+    pub async fn challenges_ending_soon(
         &self,
-        ty: NanoKind,
-        filter: &[(&str, u64)],
-    ) -> Result<CollectionResponse<D>, Error> {
-        self.get_all_include_filtered(ty, &[], filter).await
+        within_days: i64,
+    ) -> Result<CollectionResponse<ProjectChallengeObject>, Error> {
+        if !self.is_logged_in().await {
+            return Err(Error::NoCredentials);
+        };
+
+        let user_id = self.current_user().await?.data.id;
+        let today = Utc::now().date_naive();
+        // No challenge runs for a century, so any more makes no difference, and very large
+        // numbers of days can't be added to a date
+        let within_days = within_days.clamp(-MAX_CHALLENGE_DAYS, MAX_CHALLENGE_DAYS);
+        let until = today + chrono::Duration::days(within_days);
+
+        let mut data = self
+            .stream_all::<ProjectChallengeObject>(
+                NanoKind::ProjectChallenge,
+                &[("user_id", user_id)],
+            )
+            .try_filter(|project_challenge| {
+                let attributes = &project_challenge.attributes;
+                future::ready(
+                    attributes.is_running_on(today)
+                        && attributes.ends_at <= until
+                        && !attributes.has_won(),
+                )
+            })
+            .try_collect::<Vec<_>>()
+            .await?;
+
+        data.sort_by_key(|project_challenge| project_challenge.attributes.ends_at);
+
+        Ok(CollectionResponse {
+            data,
+            included: None,
+            meta: None,
+            links: None,
+            post_info: None,
+            extra: Default::default(),
+        })
+    }
+
+    /// Get the writing streak for a given ProjectChallenge, computed from its daily aggregates
+    /// (See [`StreakInfo`])
+    pub async fn writing_streak(&self, project_challenge_id: u64) -> Result<StreakInfo, Error> {
+        let aggregates = self.daily_aggregates(project_challenge_id).await?;
+
+        Ok(StreakInfo::from_aggregates(
+            &aggregates.data,
+            Utc::now().date_naive(),
+        ))
+    }
+
+    /// Get the writing sessions logged against a project, in chronological order of their
+    /// session date
+    pub async fn project_sessions(
+        &self,
+        project_id: u64,
+    ) -> Result<CollectionResponse<ProjectSessionObject>, Error> {
+        let mut sessions = self
+            .get_all_filtered::<ProjectSessionObject>(
+                NanoKind::ProjectSession,
+                &[("project_id", project_id)],
+            )
+            .await?;
+
+        sessions.data.sort_by_key(|session| {
+            (
+                session.attributes.session_date,
+                session.attributes.created_at,
+            )
+        });
+
+        Ok(sessions)
+    }
+
+    /// Stream every writing session logged against a project, fetching further pages as needed.
+    /// Unlike [`Self::project_sessions`], sessions come in the order the API returns them.
+    pub fn stream_sessions(
+        &self,
+        project_id: u64,
+    ) -> impl Stream<Item = Result<ProjectSessionObject, Error>> + '_ {
+        paginate(move |page| async move {
+            self.get_all_paginated(
+                NanoKind::ProjectSession,
+                &[("project_id", project_id)],
+                page,
+            )
+            .await
+        })
+    }
+
+    /// Get the challenge a project challenge is for
+    pub async fn challenge_for(
+        &self,
+        project_challenge: &ProjectChallengeObject,
+    ) -> Result<ItemResponse<ChallengeObject>, Error> {
+        self.get_id(
+            NanoKind::Challenge,
+            project_challenge.attributes.challenge_id,
+        )
+        .await
+    }
+
+    /// Get the project a project challenge is part of
+    pub async fn project_for(
+        &self,
+        project_challenge: &ProjectChallengeObject,
+    ) -> Result<ItemResponse<ProjectObject>, Error> {
+        self.get_id(NanoKind::Project, project_challenge.attributes.project_id)
+            .await
+    }
+
+    /// Check whether the goal of a project challenge has been reached (see
+    /// [`ProjectChallengeData::has_won`])
+    pub async fn check_win(&self, project_challenge_id: u64) -> Result<bool, Error> {
+        let project_challenge = self
+            .get_id::<ProjectChallengeObject>(NanoKind::ProjectChallenge, project_challenge_id)
+            .await?;
+
+        Ok(project_challenge.data.attributes.has_won())
+    }
+
+    /// Get the current count of a project challenge, and nothing else. This is much lighter than
+    /// getting the whole project challenge, so is suitable for polling.
+    pub async fn current_word_count(&self, project_challenge_id: u64) -> Result<u64, Error> {
+        let kind = NanoKind::ProjectChallenge.api_name();
+
+        let resp = self
+            .retry_request::<_, serde_json::Value>(
+                &format!("{}/{}", kind, project_challenge_id),
+                Method::GET,
+                &[(format!("fields[{}]", kind), "current-count")],
+            )
+            .await?;
+
+        let count = resp
+            .pointer("/data/attributes/current-count")
+            .cloned()
+            .unwrap_or_default();
+
+        Ok(serde_json::from_value(count)?)
+    }
+
+    /// Export all of a user's data into a single snapshot: the user, their projects, the
+    /// challenges and sessions of those projects, and the badges they've earned. This makes a
+    /// number of requests, which grows with the number of projects the user has.
+    pub async fn export_user(&self, user_id: u64) -> Result<UserExport, Error> {
+        let user = self
+            .get_id::<UserObject>(NanoKind::User, user_id)
+            .await?
+            .data;
+
+        let projects = self
+            .stream_all::<ProjectObject>(NanoKind::Project, &[("user_id", user_id)])
+            .try_collect::<Vec<_>>()
+            .await?;
+
+        let mut project_challenges = Vec::new();
+        let mut project_sessions = Vec::new();
+        for project in &projects {
+            let filter = [("project_id", project.id)];
+
+            project_challenges.extend(
+                self.stream_all::<ProjectChallengeObject>(NanoKind::ProjectChallenge, &filter)
+                    .try_collect::<Vec<_>>()
+                    .await?,
+            );
+            project_sessions.extend(
+                self.stream_all::<ProjectSessionObject>(NanoKind::ProjectSession, &filter)
+                    .try_collect::<Vec<_>>()
+                    .await?,
+            );
+        }
+
+        let badges = self
+            .stream_all::<UserBadgeObject>(NanoKind::UserBadge, &[("user_id", user_id)])
+            .try_collect::<Vec<_>>()
+            .await?;
+
+        Ok(UserExport {
+            user,
+            projects,
+            project_challenges,
+            project_sessions,
+            badges,
+        })
+    }
+
+    /// Get the current user's timers which are still running, that is those which haven't been
+    /// cancelled and whose duration hasn't yet elapsed
+    pub async fn active_timers(&self) -> Result<Vec<TimerObject>, Error> {
+        if !self.is_logged_in().await {
+            return Err(Error::NoCredentials);
+        };
+
+        let now = Utc::now();
+        let timers = self.get_all::<TimerObject>(NanoKind::Timer).await?;
+
+        Ok(timers
+            .data
+            .into_iter()
+            .filter(|timer| {
+                !timer.attributes.cancelled
+                    && timer.attributes.start + timer.attributes.duration > now
+            })
+            .collect())
+    }
+
+    /// Get the current user's stopwatches which are still running, that is those which haven't
+    /// been stopped yet
+    pub async fn active_stopwatches(&self) -> Result<Vec<StopWatchObject>, Error> {
+        if !self.is_logged_in().await {
+            return Err(Error::NoCredentials);
+        };
+
+        let stopwatches = self.get_all::<StopWatchObject>(NanoKind::StopWatch).await?;
+
+        Ok(stopwatches
+            .data
+            .into_iter()
+            .filter(|stopwatch| stopwatch.attributes.stop.is_none())
+            .collect())
+    }
+
+    /// Get the location groups of a given group, which tie a region to the physical locations
+    /// it's associated with
+    pub async fn location_groups(
+        &self,
+        group_id: u64,
+    ) -> Result<CollectionResponse<LocationGroupObject>, Error> {
+        self.get_all_filtered(NanoKind::LocationGroup, &[("group_id", group_id)])
+            .await
+    }
+
+    /// Get the primary location of a given group, if it has one
+    pub async fn primary_location(
+        &self,
+        group_id: u64,
+    ) -> Result<Option<ItemResponse<LocationObject>>, Error> {
+        let location_groups = self.location_groups(group_id).await?;
+
+        let Some(primary) = location_groups
+            .data
+            .iter()
+            .find(|location_group| location_group.attributes.primary)
+        else {
+            return Ok(None);
+        };
+
+        self.get_id(NanoKind::Location, primary.attributes.location_id)
+            .await
+            .map(Some)
+    }
+
+    /// Get the full catalog of badges, indexed by their ID. The catalog is only fetched once,
+    /// and then kept for the lifetime of this client (and any of its clones)
+    pub async fn all_badges(&self) -> Result<HashMap<u64, BadgeObject>, Error> {
+        self.badges
+            .get_or_try_init(|| async {
+                let badges = self.get_all::<BadgeObject>(NanoKind::Badge).await?;
+
+                Ok::<_, Error>(
+                    badges
+                        .data
+                        .into_iter()
+                        .map(|badge| (badge.id, badge))
+                        .collect(),
+                )
+            })
+            .await
+            .cloned()
+    }
+
+    /// Get the badge awarded for winning a challenge, if there is one. Custom challenges don't
+    /// have a winner badge.
+    ///
+    /// This is a guess: the API doesn't tie badges to challenges or to kinds of events, so the
+    /// badge is picked by its title. Of the active winner badges, those with the word "Camp" in
+    /// their title are taken to be for Camp NaNoWriMo, and the others for NaNoWriMo. This may pick
+    /// the wrong badge, or none, if the titles of the badges change.
+    pub async fn winner_badge(&self, challenge_id: u64) -> Result<Option<BadgeObject>, Error> {
+        let challenge = self
+            .get_id::<ChallengeObject>(NanoKind::Challenge, challenge_id)
+            .await?
+            .data;
+
+        let Some(event_type) = challenge.attributes.event_type else {
+            return Ok(None);
+        };
+
+        Ok(pick_winner_badge(
+            self.all_badges().await?.into_values(),
+            event_type,
+        ))
+    }
+
+    /// Get the genres of a project, in a single request by including them with the project
+    pub async fn project_genres(
+        &self,
+        project_id: u64,
+    ) -> Result<CollectionResponse<GenreObject>, Error> {
+        let project = self
+            .get_id_include::<ProjectObject>(NanoKind::Project, project_id, &[NanoKind::Genre])
+            .await?;
+
+        let genres = project
+            .included
+            .unwrap_or_default()
+            .into_iter()
+            .filter_map(|obj| match obj {
+                Object::Genre(genre) => Some(genre),
+                _ => None,
+            })
+            .collect();
+
+        Ok(CollectionResponse {
+            data: genres,
+            included: None,
+            meta: None,
+            links: None,
+            post_info: None,
+            extra: Default::default(),
+        })
+    }
+
+    /// Check whether a project slug is still free to use, that is whether no project with this
+    /// slug exists yet
+    pub async fn project_slug_available(&self, slug: &Slug) -> Result<bool, Error> {
+        match self
+            .get_slug::<ProjectObject>(NanoKind::Project, slug)
+            .await
+        {
+            Ok(_) => Ok(false),
+            Err(Error::SimpleNanoError(code, _)) if code == StatusCode::NOT_FOUND => Ok(true),
+            Err(err) => Err(err),
+        }
+    }
+
+    /// Fetch an image from its URL, such as a [`ProjectData::cover`] or a [`UserData::avatar`].
+    /// This reuses the client's connection pool, but doesn't send the authorization token, as
+    /// images are generally hosted elsewhere than the API. Error statuses from the host of the
+    /// image are returned as [`Error::ReqwestError`].
+    pub async fn fetch_image(&self, url: &str) -> Result<Image, Error> {
+        trace!(?url, "fetching image");
+
+        let resp = self.client.get(url).send().await?.error_for_status()?;
+
+        let content_type = resp
+            .headers()
+            .get(reqwest::header::CONTENT_TYPE)
+            .and_then(|val| val.to_str().ok())
+            .map(ToString::to_string);
+
+        let bytes = self.read_body(resp).await?;
+
+        Ok(Image {
+            bytes,
+            content_type,
+        })
+    }
+
+    /// Get the IDs of the current members of a group, across all pages of its memberships
+    async fn group_member_ids(&self, group_id: u64) -> Result<Vec<u64>, Error> {
+        Ok(self
+            .stream_all::<GroupUserObject>(NanoKind::GroupUser, &[("group_id", group_id)])
+            .try_collect::<Vec<_>>()
+            .await?
+            .into_iter()
+            .filter(|member| member.attributes.exit_at.is_none())
+            .map(|member| member.attributes.user_id)
+            .collect())
+    }
+
+    /// Get the ranking of the members of a region in a challenge, by their word count, highest
+    /// first. Members who aren't taking part in the challenge, or who hide their word count or
+    /// their projects, are left out. This fetches every member and their challenges, so makes a
+    /// lot of requests for large regions.
+    pub async fn region_leaderboard(
+        &self,
+        group_id: u64,
+        challenge_id: u64,
+    ) -> Result<Vec<(UserObject, u64)>, Error> {
+        let members = self.group_member_ids(group_id).await?;
+
+        let entries = stream::iter(members.into_iter().map(|user_id| async move {
+            let user = self
+                .get_id::<UserObject>(NanoKind::User, user_id)
+                .await?
+                .data;
+
+            let hidden = !user.attributes.stats.word_count_enabled
+                || user
+                    .attributes
+                    .privacy_settings
+                    .as_ref()
+                    .is_some_and(|privacy| privacy.view_projects == PrivacySetting::Private);
+            if hidden {
+                return Ok(None);
+            }
+
+            let count = self
+                .stream_all::<ProjectChallengeObject>(
+                    NanoKind::ProjectChallenge,
+                    &[("user_id", user_id)],
+                )
+                .try_collect::<Vec<_>>()
+                .await?
+                .into_iter()
+                .filter(|project_challenge| {
+                    project_challenge.attributes.challenge_id == challenge_id
+                        && project_challenge.attributes.unit_type == UnitType::Words
+                })
+                .map(|project_challenge| project_challenge.attributes.current_count)
+                .reduce(|a, b| a + b);
+
+            Ok::<_, Error>(count.map(|count| (user, count)))
+        }))
+        .buffer_unordered(CONCURRENCY)
+        .try_collect::<Vec<_>>()
+        .await?;
+
+        let mut leaderboard = entries.into_iter().flatten().collect::<Vec<_>>();
+        leaderboard.sort_by_key(|(_, count)| std::cmp::Reverse(*count));
+
+        Ok(leaderboard)
+    }
+
+    /// Get a region by its slug, as found in region URLs, along with its members: their
+    /// memberships and users are in the included items of the response
+    pub async fn region_by_slug(&self, slug: &Slug) -> Result<ItemResponse<GroupObject>, Error> {
+        self.retry_request(
+            &format!("{}/{}", NanoKind::Group.api_name(), slug),
+            Method::GET,
+            &[("include", REGION_INCLUDES)],
+        )
+        .await
+    }
+
+    /// Get the current user's home region, that is the region they're a member of which is
+    /// flagged as their primary one, if they have one
+    pub async fn home_region(&self) -> Result<Option<GroupObject>, Error> {
+        if !self.is_logged_in().await {
+            return Err(Error::NoCredentials);
+        };
+
+        let user_id = self.current_user().await?.data.id;
+
+        let memberships = self
+            .stream_all::<GroupUserObject>(NanoKind::GroupUser, &[("user_id", user_id)])
+            .try_collect::<Vec<_>>()
+            .await?;
+
+        let Some(home) = memberships.iter().find(|membership| {
+            membership.attributes.group_type == GroupType::Region
+                && membership.attributes.primary
+                && membership.attributes.exit_at.is_none()
+        }) else {
+            return Ok(None);
+        };
+
+        self.get_id::<GroupObject>(NanoKind::Group, home.attributes.group_id)
+            .await
+            .map(|group| Some(group.data))
+    }
+
+    /// Get all the links of a group: its forum, its website, and its external links, leaving out
+    /// any which aren't valid web URLs, and those listed more than once
+    #[cfg(feature = "url")]
+    pub async fn group_links(&self, group_id: u64) -> Result<Vec<url::Url>, Error> {
+        let (group, external) = future::try_join(
+            self.get_id::<GroupObject>(NanoKind::Group, group_id),
+            self.stream_all::<GroupExternalLinkObject>(
+                NanoKind::GroupExternalLink,
+                &[("group_id", group_id)],
+            )
+            .try_collect::<Vec<_>>(),
+        )
+        .await?;
+
+        let links = [
+            group.data.attributes.forum_link(),
+            group.data.attributes.website_link(),
+        ]
+        .into_iter()
+        .flatten()
+        .chain(external.iter().filter_map(|link| link.attributes.link()));
+
+        let mut seen = HashSet::new();
+        Ok(links.filter(|link| seen.insert(link.clone())).collect())
+    }
+
+    /// Get the IDs of the buddy groups a user is in, each tying them to one buddy
+    async fn buddy_group_ids(&self, user_id: u64) -> Result<Vec<u64>, Error> {
+        Ok(self
+            .stream_all::<GroupUserObject>(NanoKind::GroupUser, &[("user_id", user_id)])
+            .try_collect::<Vec<_>>()
+            .await?
+            .into_iter()
+            .filter(|membership| {
+                membership.attributes.group_type == GroupType::Buddies
+                    && membership.attributes.invitation_accepted == InvitationStatus::Accepted
+                    && membership.attributes.exit_at.is_none()
+            })
+            .map(|membership| membership.attributes.group_id)
+            .collect())
+    }
+
+    /// Get the direct messages exchanged between the current user and one of their buddies,
+    /// that is the nanomessages of the buddy group they share. There are none if they aren't
+    /// buddies.
+    pub async fn direct_messages(
+        &self,
+        other_user_id: u64,
+    ) -> Result<CollectionResponse<NanoMessageObject>, Error> {
+        if !self.is_logged_in().await {
+            return Err(Error::NoCredentials);
+        };
+
+        let user_id = self.current_user().await?.data.id;
+
+        let (own, theirs) = future::try_join(
+            self.buddy_group_ids(user_id),
+            self.buddy_group_ids(other_user_id),
+        )
+        .await?;
+
+        let Some(&group_id) = own.iter().find(|group_id| theirs.contains(group_id)) else {
+            return Ok(CollectionResponse {
+                data: Vec::new(),
+                included: None,
+                meta: None,
+                links: None,
+                post_info: None,
+                extra: Default::default(),
+            });
+        };
+
+        self.get_all_filtered(NanoKind::NanoMessage, &[("group_id", group_id)])
+            .await
+    }
+
+    /// Send a nanomessage to a group as the current user, such as a region or a buddy group.
+    /// With `send_email`, the members of the group are also notified by email.
+    pub async fn send_nanomessage(
+        &self,
+        group_id: u64,
+        content: &str,
+        send_email: bool,
+    ) -> Result<ItemResponse<NanoMessageObject>, Error> {
+        if !self.is_logged_in().await {
+            return Err(Error::NoCredentials);
+        };
+
+        let user_id = self.current_user().await?.data.id;
+
+        let data = json!({
+            "data": {
+                "type": NanoKind::NanoMessage.api_name(),
+                "attributes": {
+                    "content": content,
+                    "group-id": group_id,
+                    "official": false,
+                    "send-email": send_email,
+                    "user-id": user_id,
+                },
+                "relationships": relate_to(&[
+                    (NanoKind::Group, group_id),
+                    (NanoKind::User, user_id),
+                ]),
+            }
+        });
+
+        self.retry_request(
+            NanoKind::NanoMessage.api_name(),
+            Method::POST,
+            &write_view(&data)?,
+        )
+        .await
+    }
+
+    /// Get the IDs of the current user's buddies, that is the other members of the buddy groups
+    /// the user is in
+    pub async fn buddy_ids(&self) -> Result<Vec<u64>, Error> {
+        if !self.is_logged_in().await {
+            return Err(Error::NoCredentials);
+        };
+
+        let user_id = self.current_user().await?.data.id;
+        let groups = self.buddy_group_ids(user_id).await?;
+
+        let members = stream::iter(
+            groups
+                .into_iter()
+                .map(|group_id| self.group_member_ids(group_id)),
+        )
+        .buffer_unordered(CONCURRENCY)
+        .try_collect::<Vec<_>>()
+        .await?;
+
+        let mut buddies = members
+            .into_iter()
+            .flatten()
+            .filter(|&id| id != user_id)
+            .collect::<Vec<_>>();
+        buddies.sort_unstable();
+        buddies.dedup();
+
+        Ok(buddies)
+    }
+
+    /// Check whether a user is one of the current user's buddies
+    pub async fn is_buddy(&self, user_id: u64) -> Result<bool, Error> {
+        Ok(self.buddy_ids().await?.contains(&user_id))
+    }
+
+    /// Create the buddies group membership tying the current user to another user, in the given
+    /// state
+    async fn create_buddy_membership(
+        &self,
+        user_id: u64,
+        entry_method: EntryMethod,
+        status: InvitationStatus,
+    ) -> Result<(), Error> {
+        if !self.is_logged_in().await {
+            return Err(Error::NoCredentials);
+        };
+
+        let current_id = self.current_user().await?.data.id;
+
+        let data = json!({
+            "data": {
+                "type": NanoKind::GroupUser.api_name(),
+                "attributes": {
+                    "entry-method": entry_method,
+                    "group-type": GroupType::Buddies,
+                    "invitation-accepted": status,
+                    "invited-by-id": current_id,
+                    "user-id": user_id,
+                },
+                "relationships": relate_to(&[(NanoKind::User, user_id)]),
+            }
+        });
+
+        self.retry_request::<_, IgnoredAny>(
+            NanoKind::GroupUser.api_name(),
+            Method::POST,
+            &write_view(&data)?,
+        )
+        .await?;
+
+        Ok(())
+    }
+
+    /// Remove a group membership
+    async fn delete_group_user(&self, group_user_id: u64) -> Result<(), Error> {
+        if !self.is_logged_in().await {
+            return Err(Error::NoCredentials);
+        };
+
+        self.retry_request::<_, IgnoredAny>(
+            &format!("{}/{}", NanoKind::GroupUser.api_name(), group_user_id),
+            Method::DELETE,
+            &(),
+        )
+        .await?;
+
+        Ok(())
+    }
+
+    /// Ask a user to become the current user's buddy. They become buddies once the user accepts
+    /// (see [`Self::accept_buddy_request`]).
+    pub async fn send_buddy_request(&self, user_id: u64) -> Result<(), Error> {
+        self.create_buddy_membership(user_id, EntryMethod::Invited, InvitationStatus::Sent)
+            .await
+    }
+
+    /// Accept a buddy request, given the group membership the request created for the current
+    /// user
+    pub async fn accept_buddy_request(&self, group_user_id: u64) -> Result<(), Error> {
+        self.update_attributes::<GroupUserObject>(
+            NanoKind::GroupUser,
+            group_user_id,
+            json!({ "invitation-accepted": InvitationStatus::Accepted }),
+        )
+        .await?;
+
+        Ok(())
+    }
+
+    /// Decline a buddy request, given the group membership the request created for the current
+    /// user. The membership is removed.
+    pub async fn decline_buddy_request(&self, group_user_id: u64) -> Result<(), Error> {
+        self.delete_group_user(group_user_id).await
+    }
+
+    /// Block a user, so they can't send buddy requests or messages to the current user
+    pub async fn block_user(&self, user_id: u64) -> Result<(), Error> {
+        self.create_buddy_membership(user_id, EntryMethod::Blocked, InvitationStatus::Blocked)
+            .await
+    }
+
+    /// Unblock a user previously blocked by the current user (see [`Self::block_user`])
+    pub async fn unblock_user(&self, user_id: u64) -> Result<(), Error> {
+        if !self.is_logged_in().await {
+            return Err(Error::NoCredentials);
+        };
+
+        let current_id = self.current_user().await?.data.id;
+
+        let blocks = self
+            .stream_all::<GroupUserObject>(NanoKind::GroupUser, &[("user_id", user_id)])
+            .try_collect::<Vec<_>>()
+            .await?
+            .into_iter()
+            .filter(|membership| {
+                membership.attributes.group_type == GroupType::Buddies
+                    && membership.attributes.invitation_accepted == InvitationStatus::Blocked
+                    && membership.attributes.invited_by_id == Some(current_id)
+            });
+
+        for block in blocks {
+            self.delete_group_user(block.id).await?;
+        }
+
+        Ok(())
+    }
+
+    /// Get what the current user's buddies have been up to: the writing sessions they logged in
+    /// the past week, most recent first. Buddies who hide their projects or their activity are
+    /// left out. This fetches the projects and sessions of every buddy, so makes a lot of
+    /// requests.
+    pub async fn buddy_activity(&self) -> Result<Vec<ActivityItem>, Error> {
+        let buddies = self.buddy_ids().await?;
+        let since = Utc::now() - chrono::Duration::days(ACTIVITY_DAYS);
+
+        let feeds = stream::iter(buddies.into_iter().map(|user_id| async move {
+            let user = self
+                .get_id::<UserObject>(NanoKind::User, user_id)
+                .await?
+                .data;
+
+            let hidden = user
+                .attributes
+                .privacy_settings
+                .as_ref()
+                .is_some_and(|privacy| {
+                    privacy.view_projects == PrivacySetting::Private
+                        || !privacy.visibility_activity_logs
+                });
+            if hidden {
+                return Ok(Vec::new());
+            }
+
+            let projects = self
+                .stream_all::<ProjectObject>(NanoKind::Project, &[("user_id", user_id)])
+                .try_collect::<Vec<_>>()
+                .await?;
+
+            let mut items = Vec::new();
+            for project in projects {
+                let sessions = self
+                    .stream_all::<ProjectSessionObject>(
+                        NanoKind::ProjectSession,
+                        &[("project_id", project.id)],
+                    )
+                    .try_collect::<Vec<_>>()
+                    .await?;
+
+                items.extend(sessions.into_iter().filter_map(|session| {
+                    let at = session
+                        .attributes
+                        .end
+                        .or(session.attributes.start)
+                        .or(session.attributes.created_at)?;
+
+                    (at >= since).then_some(ActivityItem {
+                        user_id,
+                        project_id: project.id,
+                        at,
+                        session,
+                    })
+                }));
+            }
+
+            Ok::<_, Error>(items)
+        }))
+        .buffer_unordered(CONCURRENCY)
+        .try_collect::<Vec<_>>()
+        .await?;
+
+        let mut activity = feeds.into_iter().flatten().collect::<Vec<_>>();
+        activity.sort_by_key(|item| std::cmp::Reverse(item.at));
+
+        Ok(activity)
+    }
+
+    /// Get the projects of a user, respecting their privacy settings. If the user hides their
+    /// projects from the current user, this fails with [`Error::PrivacyRestricted`] rather than
+    /// returning an empty collection.
+    pub async fn user_projects(
+        &self,
+        user_id: u64,
+    ) -> Result<CollectionResponse<ProjectObject>, Error> {
+        let user = self
+            .get_id::<UserObject>(NanoKind::User, user_id)
+            .await?
+            .data;
+        let setting = user
+            .attributes
+            .privacy_settings
+            .as_ref()
+            .map(|privacy| privacy.view_projects);
+
+        let logged_in = self.is_logged_in().await;
+        let own = logged_in && self.current_user().await?.data.id == user_id;
+        if !own {
+            let allowed = match setting {
+                Some(PrivacySetting::Private) => false,
+                Some(PrivacySetting::Buddies) => {
+                    logged_in && self.buddy_ids().await?.contains(&user_id)
+                }
+                _ => true,
+            };
+
+            if let (false, Some(setting)) = (allowed, setting) {
+                return Err(Error::PrivacyRestricted { setting });
+            }
+        }
+
+        match self
+            .get_all_filtered(NanoKind::Project, &[("user_id", user_id)])
+            .await
+        {
+            Err(err) if err.is_forbidden() => Err(Error::PrivacyRestricted {
+                setting: setting.unwrap_or_default(),
+            }),
+            res => res,
+        }
+    }
+
+    /// Get the favorite books and authors of all of the current user's buddies, leaving out
+    /// those of buddies who keep their profile private. The favorites of each buddy are fetched
+    /// concurrently.
+    pub async fn buddies_favorites(
+        &self,
+    ) -> Result<(Vec<FavoriteBookObject>, Vec<FavoriteAuthorObject>), Error> {
+        let buddies = self.buddy_ids().await?;
+
+        let favorites = stream::iter(buddies.into_iter().map(|user_id| async move {
+            let user = self
+                .get_id::<UserObject>(NanoKind::User, user_id)
+                .await?
+                .data;
+            let private = user
+                .attributes
+                .privacy_settings
+                .is_some_and(|privacy| privacy.view_profile == PrivacySetting::Private);
+            if private {
+                return Ok((Vec::new(), Vec::new()));
+            }
+
+            let filter = [("user_id", user_id)];
+            let favorites = future::try_join(
+                self.stream_all::<FavoriteBookObject>(NanoKind::FavoriteBook, &filter)
+                    .try_collect::<Vec<_>>(),
+                self.stream_all::<FavoriteAuthorObject>(NanoKind::FavoriteAuthor, &filter)
+                    .try_collect::<Vec<_>>(),
+            )
+            .await;
+
+            match favorites {
+                Err(err) if err.is_forbidden() => Ok((Vec::new(), Vec::new())),
+                res => res,
+            }
+        }))
+        .buffer_unordered(CONCURRENCY)
+        .try_collect::<Vec<_>>()
+        .await?;
+
+        let (books, authors): (Vec<_>, Vec<_>) = favorites.into_iter().unzip();
+        Ok((
+            books.into_iter().flatten().collect(),
+            authors.into_iter().flatten().collect(),
+        ))
+    }
+
+    /// Get aggregate statistics about a region: how many of its members are taking part in a
+    /// currently running challenge, and how many words they've written in it. This fetches the
+    /// challenges of every member, so makes a lot of requests for large regions.
+    pub async fn region_stats(&self, group_id: u64) -> Result<RegionStats, Error> {
+        let members = self.group_member_ids(group_id).await?;
+        let today = Utc::now().date_naive();
+
+        let counts = stream::iter(members.iter().map(|&user_id| async move {
+            let challenges = self
+                .stream_all::<ProjectChallengeObject>(
+                    NanoKind::ProjectChallenge,
+                    &[("user_id", user_id)],
+                )
+                .try_collect::<Vec<_>>()
+                .await?;
+
+            Ok::<_, Error>(
+                challenges
+                    .into_iter()
+                    .filter(|challenge| {
+                        challenge.attributes.unit_type == UnitType::Words
+                            && challenge.attributes.is_running_on(today)
+                    })
+                    .map(|challenge| challenge.attributes.current_count)
+                    .reduce(|a, b| a + b),
+            )
+        }))
+        .buffer_unordered(CONCURRENCY)
+        .try_collect::<Vec<_>>()
+        .await?;
+
+        let mut stats = RegionStats {
+            members: members.len() as u64,
+            ..Default::default()
+        };
+
+        for count in counts.into_iter().flatten() {
+            stats.participants += 1;
+            stats.total_words += count;
+        }
+
+        if stats.participants > 0 {
+            stats.average_words = stats.total_words as f64 / stats.participants as f64;
+        }
+
+        Ok(stats)
+    }
+
+    // Type queries
+
+    /// Get all accessible items of a specific kind, with included linked items and filtering to
+    /// certain related IDs.
+    ///
+    /// 'includes' will add more items in the response as part of an 'includes' list,
+    /// so one request can get more items. If it's empty, the client's default includes for the
+    /// kind are used instead (See [`Self::set_default_includes`])
+    ///
+    /// 'filter' will filter certain types of objects by IDs of other objects related to them.
+    ///
+    /// **Warning**: Many filter combinations are invalid, and the rules are not currently fully
+    /// understood.
+    pub async fn get_all_include_filtered<D: ObjectInfo + DeserializeOwned>(
+        &self,
+        ty: NanoKind,
+        include: &[NanoKind],
+        filter: &[(&str, u64)],
+    ) -> Result<CollectionResponse<D>, Error> {
+        let mut data = Vec::new();
+
+        for i in filter {
+            data.push((format!("filter[{}]", i.0), i.1.to_string()))
+        }
+
+        add_included(&mut data, &self.includes_for(ty, include));
+
+        self.retry_request(ty.api_name(), Method::GET, &data).await
+    }
+
+    /// Get all accessible items of a specific kind, with filtering to certain related IDs
+    /// (See [`Self::get_all_include_filtered`])
+    pub async fn get_all_filtered<D: ObjectInfo + DeserializeOwned>(
+        &self,
+        ty: NanoKind,
+        filter: &[(&str, u64)],
+    ) -> Result<CollectionResponse<D>, Error> {
+        self.get_all_include_filtered(ty, &[], filter).await
     }
 
     /// Get all accessible items of a specific kind, with included linked items
@@ -356,7 +2117,127 @@ impl NanoClient {
         self.get_all_include_filtered(ty, &[], &[]).await
     }
 
-    /// Get an item of a specific type and ID, with included linked items
+    /// Get all accessible items of a specific kind, as just the items, without any included
+    /// linked items. (See [`Self::get_all`])
+    pub async fn list<D: ObjectInfo + DeserializeOwned>(
+        &self,
+        ty: NanoKind,
+    ) -> Result<Vec<D>, Error> {
+        Ok(self.get_all(ty).await?.data)
+    }
+
+    /// Get a specific page of the accessible items of a specific kind, with filtering to certain
+    /// related IDs. Pages start at 1. (See [`Self::get_all_include_filtered`])
+    pub async fn get_all_paginated<D: ObjectInfo + DeserializeOwned>(
+        &self,
+        ty: NanoKind,
+        filter: &[(&str, u64)],
+        page: u64,
+    ) -> Result<CollectionResponse<D>, Error> {
+        let mut data = Vec::new();
+
+        for i in filter {
+            data.push((format!("filter[{}]", i.0), i.1.to_string()))
+        }
+
+        add_included(&mut data, &self.includes_for(ty, &[]));
+        add_page(&mut data, page);
+
+        self.retry_request(ty.api_name(), Method::GET, &data).await
+    }
+
+    /// Stream all accessible items of a specific kind, with filtering to certain related IDs,
+    /// fetching further pages as needed. (See [`Self::get_all_paginated`])
+    pub fn stream_all<'a, D: ObjectInfo + DeserializeOwned + 'a>(
+        &'a self,
+        ty: NanoKind,
+        filter: &'a [(&'a str, u64)],
+    ) -> impl Stream<Item = Result<D, Error>> + 'a {
+        paginate(move |page| self.get_all_paginated(ty, filter, page))
+    }
+
+    /// Get all accessible items of a specific kind which were updated after a given time, to
+    /// mirror data without pulling all of it again. The time is sent to the API as the
+    /// `updated_since` filter, and items are also filtered on their `updated-at` attribute here,
+    /// for kinds where the API doesn't support the filter. Items of kinds without that attribute
+    /// are all returned. Linked items aren't included, even those included by default.
+    pub async fn updated_since<D: ObjectInfo + DeserializeOwned>(
+        &self,
+        ty: NanoKind,
+        since: DateTime<Utc>,
+    ) -> Result<CollectionResponse<D>, Error> {
+        #[derive(Deserialize)]
+        struct Updated {
+            #[serde(rename = "updated-at", alias = "updated_at")]
+            updated_at: Option<DateTime<Utc>>,
+        }
+
+        let since_param = since.to_rfc3339_opts(SecondsFormat::Secs, true);
+
+        let data = paginate(|page| {
+            let mut data = vec![("filter[updated_since]".to_string(), since_param.clone())];
+            add_page(&mut data, page);
+
+            async move { self.retry_request(ty.api_name(), Method::GET, &data).await }
+        })
+        .try_filter(|item: &D| {
+            let updated_at = item
+                .attributes_as::<Updated>()
+                .ok()
+                .and_then(|attributes| attributes.updated_at);
+
+            future::ready(updated_at.is_none_or(|updated_at| updated_at > since))
+        })
+        .try_collect::<Vec<_>>()
+        .await?;
+
+        Ok(CollectionResponse {
+            data,
+            included: None,
+            meta: None,
+            links: None,
+            post_info: None,
+            extra: Default::default(),
+        })
+    }
+
+    /// Stream the pages of a pull of all accessible items of a kind, starting from a cursor.
+    /// Each page comes with the cursor to resume the pull from after it, which can be persisted
+    /// so that an interrupted pull doesn't have to start over. (See [`PageCursor`])
+    pub fn stream_pages<'a, D: ObjectInfo + DeserializeOwned + 'a>(
+        &'a self,
+        cursor: PageCursor,
+    ) -> impl Stream<Item = Result<(Vec<D>, PageCursor), Error>> + 'a {
+        let PageCursor { kind, filter, page } = cursor;
+        let filter = Arc::new(filter);
+
+        paginate_pages(page, {
+            let filter = filter.clone();
+            move |page| {
+                let filter = filter.clone();
+                async move {
+                    let filter = filter
+                        .iter()
+                        .map(|(key, val)| (key.as_str(), *val))
+                        .collect::<Vec<_>>();
+
+                    self.get_all_paginated(kind, &filter, page).await
+                }
+            }
+        })
+        .map_ok(move |(items, page)| {
+            let cursor = PageCursor {
+                kind,
+                filter: filter.to_vec(),
+                page,
+            };
+
+            (items, cursor)
+        })
+    }
+
+    /// Get an item of a specific type and ID, with included linked items. If no includes are
+    /// given, the client's default includes for the kind are used instead.
     pub async fn get_id_include<D: ObjectInfo + DeserializeOwned>(
         &self,
         ty: NanoKind,
@@ -365,7 +2246,7 @@ impl NanoClient {
     ) -> Result<ItemResponse<D>, Error> {
         let mut data = Vec::new();
 
-        add_included(&mut data, include);
+        add_included(&mut data, &self.includes_for(ty, include));
 
         self.retry_request(&format!("{}/{}", ty.api_name(), id), Method::GET, &data)
             .await
@@ -381,17 +2262,60 @@ impl NanoClient {
         self.get_id_include(ty, id, &[]).await
     }
 
+    /// Get an item of a specific type and ID, as just the item, without any included linked
+    /// items. (See [`Self::get_id`])
+    pub async fn get<D: ObjectInfo + DeserializeOwned>(
+        &self,
+        ty: NanoKind,
+        id: u64,
+    ) -> Result<D, Error> {
+        Ok(self.get_id(ty, id).await?.data)
+    }
+
+    /// Refetch an item that was previously retrieved, by its kind and ID, to get its current
+    /// state. (See [`Self::get_id`])
+    pub async fn refresh<D: ObjectInfo + DeserializeOwned>(
+        &self,
+        obj: &D,
+    ) -> Result<ItemResponse<D>, Error> {
+        self.get_id(obj.kind(), obj.id()).await
+    }
+
+    /// Fetch the object an ObjectRef points to from the server. Prefer looking it up in the
+    /// included items of the response it came from first, if it may be there (see
+    /// [`CollectionResponse::get_ref`] and [`ItemResponse::get_ref`])
+    pub async fn resolve_ref(&self, obj_ref: &ObjectRef) -> Result<ItemResponse, Error> {
+        self.get_id(obj_ref.kind, obj_ref.id).await
+    }
+
+    /// Get a single attribute of an item of a specific type and ID, without decoding the rest of
+    /// the item. The attribute is named as in the API, like `current-count`. This is cheaper than
+    /// [`Self::get_id`] when polling a single value of many items.
+    #[cfg(feature = "raw_value")]
+    pub async fn get_attribute<T: DeserializeOwned + std::fmt::Debug>(
+        &self,
+        ty: NanoKind,
+        id: u64,
+        attribute: &str,
+    ) -> Result<T, Error> {
+        let raw: RawAttributes = self
+            .retry_request(&format!("{}/{}", ty.api_name(), id), Method::GET, &())
+            .await?;
+
+        Ok(raw.get(attribute)?)
+    }
+
     /// Get an item of a specific type and slug, with included items.
     /// A slug is a unique text identifier for an object, not all types have one.
     pub async fn get_slug_include<D: ObjectInfo + DeserializeOwned>(
         &self,
         ty: NanoKind,
-        slug: &str,
+        slug: &Slug,
         include: &[NanoKind],
     ) -> Result<ItemResponse<D>, Error> {
         let mut data = Vec::new();
 
-        add_included(&mut data, include);
+        add_included(&mut data, &self.includes_for(ty, include));
 
         self.retry_request(&format!("{}/{}", ty.api_name(), slug), Method::GET, &data)
             .await
@@ -402,7 +2326,7 @@ impl NanoClient {
     pub async fn get_slug<D: ObjectInfo + DeserializeOwned>(
         &self,
         ty: NanoKind,
-        slug: &str,
+        slug: &Slug,
     ) -> Result<ItemResponse<D>, Error> {
         self.get_slug_include(ty, slug, &[]).await
     }
@@ -414,7 +2338,7 @@ impl NanoClient {
     /// way Nano handle them on its end, if you know ahead of time that you will need the relations,
     /// it's better to use [`Self::get_id_include`] or [`Self::get_all_include`]
     pub async fn get_all_related(&self, rel: &RelationLink) -> Result<CollectionResponse, Error> {
-        if !rel.related.ends_with('s') {
+        if rel.is_singleton() == Some(true) {
             panic!("get_all_related can only get many-relation links")
         }
 
@@ -425,11 +2349,34 @@ impl NanoClient {
     /// specific type that are related to it. Single relations tend to not have the same pitfalls as
     /// multiple relations, so this is less dangerous than [`Self::get_all_related`]
     pub async fn get_unique_related(&self, rel: &RelationLink) -> Result<ItemResponse, Error> {
-        if rel.related.ends_with('s') {
+        if rel.is_singleton() == Some(false) {
             panic!("get_unique_related can only get single-relation links")
         }
 
-        self.retry_request(&rel.related, Method::GET, &()).await
+        // Some single relations come back as an array of one, so accept either form
+        let CollectionResponse {
+            mut data,
+            included,
+            post_info,
+            extra,
+            ..
+        } = self
+            .retry_request::<_, CollectionResponse>(&rel.related, Method::GET, &())
+            .await?;
+
+        if data.len() != 1 {
+            return Err(Error::BadJSON(serde::de::Error::invalid_length(
+                data.len(),
+                &"a single related object",
+            )));
+        }
+
+        Ok(ItemResponse {
+            data: data.remove(0),
+            included,
+            post_info,
+            extra,
+        })
     }
 
     /// Update wordcount
@@ -449,41 +2396,261 @@ impl NanoClient {
             return Err(Error::NoCredentials);
         };
 
+        let attributes = ProjectSessionData {
+            count: words,
+            ..Default::default()
+        };
+        let data = project_session_body(project_id, project_challenge_id, attributes)?;
+
+        self.retry_request("project-sessions", Method::POST, &data)
+            .await
+    }
+
+    /// Add many writing sessions at once, such as when importing a writing history from another
+    /// tool, each given by its day and count. The requests are made concurrently, and if any of
+    /// them fail, the others still go through.
+    ///
+    /// Returns the outcome of each session, in the same order as `sessions`: either the saved
+    /// project session, or why it couldn't be saved. Only the failed ones need to be retried.
+    pub async fn import_sessions(
+        &self,
+        project_id: u64,
+        project_challenge_id: u64,
+        sessions: &[(NaiveDate, i64)],
+    ) -> Result<Vec<Result<ProjectSessionObject, Error>>, Error> {
+        if !self.is_logged_in().await {
+            return Err(Error::NoCredentials);
+        };
+
+        let results = stream::iter(sessions.iter().map(|&(day, count)| async move {
+            let attributes = ProjectSessionData {
+                count,
+                session_date: Some(day),
+                ..Default::default()
+            };
+            let data = project_session_body(project_id, project_challenge_id, attributes)?;
+
+            self.retry_request::<_, ItemResponse<ProjectSessionObject>>(
+                "project-sessions",
+                Method::POST,
+                &data,
+            )
+            .await
+        }))
+        .buffered(CONCURRENCY)
+        .map_ok(|session| session.data)
+        .collect()
+        .await;
+
+        Ok(results)
+    }
+
+    /// Add a writing session which happened at a known time, along with how it went. Works
+    /// like [`Self::add_project_session`] otherwise.
+    ///
+    /// The words are counted towards the day the session started on in the time zone of `start`,
+    /// so that should be the time zone of the user: a session started just after midnight for
+    /// them counts for that day, even if it's still the day before in UTC.
+    ///
+    /// Returns the saved project session.
+    #[allow(clippy::too_many_arguments)]
+    pub async fn add_timed_session<Tz: TimeZone>(
+        &self,
+        project_id: u64,
+        project_challenge_id: u64,
+        count: i64,
+        start: DateTime<Tz>,
+        end: DateTime<Tz>,
+        feeling: Option<Feeling>,
+        how: Option<How>,
+        where_: Option<Where>,
+    ) -> Result<ItemResponse<ProjectSessionObject>, Error> {
+        if !self.is_logged_in().await {
+            return Err(Error::NoCredentials);
+        };
+
+        let attributes = ProjectSessionData {
+            feeling,
+            how,
+            r#where: where_,
+            ..timed_session_data(count, start, end)
+        };
+        let data = project_session_body(project_id, project_challenge_id, attributes)?;
+
+        self.retry_request("project-sessions", Method::POST, &data)
+            .await
+    }
+
+    /// Update only some attributes of an object, leaving the others untouched
+    async fn update_attributes<D: ObjectInfo + DeserializeOwned>(
+        &self,
+        ty: NanoKind,
+        id: u64,
+        attributes: serde_json::Value,
+    ) -> Result<ItemResponse<D>, Error> {
+        if !self.is_logged_in().await {
+            return Err(Error::NoCredentials);
+        };
+
+        let data = json!({
+            "data": {
+                "type": ty.api_name(),
+                "id": id.to_string(),
+                "attributes": attributes,
+            }
+        });
+
+        self.retry_request(
+            &format!("{}/{}", ty.api_name(), id),
+            Method::PATCH,
+            &update_view(&data)?,
+        )
+        .await
+    }
+
+    /// Get the projects tagged with a given genre
+    pub async fn projects_by_genre(
+        &self,
+        genre_id: u64,
+    ) -> Result<CollectionResponse<ProjectObject>, Error> {
+        self.get_all_filtered(NanoKind::Project, &[("genre_id", genre_id)])
+            .await
+    }
+
+    /// Get a project along with the goal of the challenge it's currently taking part in, if
+    /// it's taking part in one
+    pub async fn project_with_goal(
+        &self,
+        project_id: u64,
+    ) -> Result<(ProjectObject, Option<u64>), Error> {
+        let filter = [("project_id", project_id)];
+        let (project, project_challenges) = future::try_join(
+            self.get_id::<ProjectObject>(NanoKind::Project, project_id),
+            self.get_all_filtered::<ProjectChallengeObject>(NanoKind::ProjectChallenge, &filter),
+        )
+        .await?;
+
+        let today = Utc::now().date_naive();
+        let goal = project_challenges
+            .data
+            .iter()
+            .find(|project_challenge| project_challenge.attributes.is_running_on(today))
+            .map(|project_challenge| project_challenge.attributes.goal);
+
+        Ok((project.data, goal))
+    }
+
+    /// Change who can see a project, leaving its other attributes untouched
+    pub async fn set_project_privacy(
+        &self,
+        project_id: u64,
+        privacy: PrivacySetting,
+    ) -> Result<(), Error> {
+        self.update_attributes::<ProjectObject>(
+            NanoKind::Project,
+            project_id,
+            json!({ "privacy": privacy }),
+        )
+        .await?;
+
+        Ok(())
+    }
+
+    /// Join a challenge with a project, creating the ProjectChallenge which ties them together.
+    /// If no goal is given, the challenge's default goal is used.
+    ///
+    /// Returns the created project challenge.
+    pub async fn join_challenge(
+        &self,
+        challenge_id: u64,
+        project_id: u64,
+        goal: Option<u64>,
+    ) -> Result<ItemResponse<ProjectChallengeObject>, Error> {
+        if !self.is_logged_in().await {
+            return Err(Error::NoCredentials);
+        };
+
+        let (challenge, project) = future::try_join(
+            self.get_id::<ChallengeObject>(NanoKind::Challenge, challenge_id),
+            self.get_id::<ProjectObject>(NanoKind::Project, project_id),
+        )
+        .await?;
+        let challenge = challenge.data.attributes;
+
         let data = ItemResponse {
-            data: Object::ProjectSession(ProjectSessionObject {
+            data: Object::ProjectChallenge(ProjectChallengeObject {
                 id: 0,
                 links: None,
-                attributes: ProjectSessionData {
-                    count: words,
+                attributes: ProjectChallengeData {
+                    challenge_id,
+                    ends_at: challenge.ends_at,
+                    event_type: challenge.event_type.unwrap_or(EventType::Custom),
+                    goal: goal.unwrap_or(challenge.default_goal),
+                    name: challenge.name,
+                    project_id,
+                    starts_at: challenge.starts_at,
+                    unit_type: challenge.unit_type,
+                    user_id: project.data.attributes.user_id,
+                    writing_type: Some(challenge.writing_type),
                     ..Default::default()
                 },
-                relationships: Some(RelationInfo {
-                    relations: Default::default(),
-                    included: vec![
-                        (
-                            NanoKind::Project,
-                            vec![ObjectRef {
-                                id: project_id,
-                                kind: NanoKind::Project,
-                            }],
-                        ),
-                        (
-                            NanoKind::ProjectChallenge,
-                            vec![ObjectRef {
-                                id: project_challenge_id,
-                                kind: NanoKind::ProjectChallenge,
-                            }],
-                        ),
-                    ]
-                    .into_iter()
-                    .collect(),
-                }),
+                relationships: relate_to(&[
+                    (NanoKind::Project, project_id),
+                    (NanoKind::Challenge, challenge_id),
+                ]),
             }),
             included: None,
             post_info: None,
+            extra: Default::default(),
         };
 
-        self.retry_request("project-sessions", Method::POST, &data)
+        self.retry_request("project-challenges", Method::POST, &write_view(&data)?)
+            .await
+    }
+
+    /// Create a custom challenge for the current user, that is a personal goal outside of the
+    /// official Nano events. It can then be joined with a project, like any other challenge
+    /// (see [`Self::join_challenge`]).
+    ///
+    /// Returns the created challenge.
+    pub async fn create_custom_challenge(
+        &self,
+        name: &str,
+        goal: u64,
+        starts_at: NaiveDate,
+        ends_at: NaiveDate,
+        unit_type: UnitType,
+        writing_type: WritingType,
+    ) -> Result<ItemResponse<ChallengeObject>, Error> {
+        if !self.is_logged_in().await {
+            return Err(Error::NoCredentials);
+        };
+
+        let user_id = self.current_user().await?.data.id;
+
+        let data = ItemResponse {
+            data: Object::Challenge(ChallengeObject {
+                id: 0,
+                links: None,
+                attributes: ChallengeData {
+                    default_goal: goal,
+                    ends_at,
+                    event_type: Some(EventType::Custom),
+                    name: name.to_string(),
+                    starts_at,
+                    unit_type,
+                    user_id,
+                    writing_type,
+                    ..Default::default()
+                },
+                relationships: relate_to(&[(NanoKind::User, user_id)]),
+            }),
+            included: None,
+            post_info: None,
+            extra: Default::default(),
+        };
+
+        self.retry_request("challenges", Method::POST, &write_view(&data)?)
             .await
     }
 }
@@ -11,6 +11,16 @@ async fn connect() {
     test_client().await;
 }
 
+#[tokio::test]
+async fn test_ping() {
+    let client = test_client().await;
+
+    assert!(
+        client.ping().await.expect("Couldn't ping the Nano API"),
+        "Freshly logged in client's token was rejected"
+    );
+}
+
 #[tokio::test]
 async fn test_current_user() {
     let client = test_client().await;
@@ -60,12 +70,33 @@ async fn test_pages() {
         "brought-to-you-by",
     ] {
         client
-            .pages(i)
+            .pages(&Slug::try_from(i).unwrap())
             .await
             .expect("Couldn't get page that was expected to exist");
     }
 }
 
+#[tokio::test]
+async fn test_search_all() {
+    let client = test_client().await;
+
+    let first = client
+        .search_paginated("craft", 1)
+        .await
+        .expect("Couldn't get first page of search results");
+
+    let all = client
+        .search_all("craft")
+        .try_collect::<Vec<_>>()
+        .await
+        .expect("Couldn't get all search results");
+
+    assert!(
+        all.len() >= first.data.len(),
+        "search_all returned fewer results than the first page"
+    );
+}
+
 #[tokio::test]
 async fn test_daily_aggregates() {
     let client = test_client().await;
@@ -76,13 +107,43 @@ async fn test_daily_aggregates() {
         .expect("Couldn't get daily aggregates");
 }
 
+#[tokio::test]
+async fn test_active_timers() {
+    let client = test_client().await;
+
+    client
+        .active_timers()
+        .await
+        .expect("Couldn't get active timers");
+
+    client
+        .active_stopwatches()
+        .await
+        .expect("Couldn't get active stopwatches");
+}
+
+#[tokio::test]
+async fn test_all_badges() {
+    let client = test_client().await;
+
+    let badges = client
+        .all_badges()
+        .await
+        .expect("Couldn't get the badge catalog");
+
+    assert!(
+        badges.contains_key(&1),
+        "Badge catalog didn't contain the example Badge"
+    );
+}
+
 #[tokio::test]
 async fn test_get_all_filtered() {
     let client = test_client().await;
     let user_id = client.current_user().await.unwrap().data.id();
 
     let projects = client
-        .get_all_filtered(NanoKind::Project, &[("user_id", user_id)])
+        .get_all_filtered::<ProjectObject>(NanoKind::Project, &[("user_id", user_id)])
         .await
         .expect("Couldn't get all filtered projects of the current user");
 
@@ -100,7 +161,7 @@ async fn test_get_id() {
     let client = test_client().await;
 
     let badge = client
-        .get_id(NanoKind::Badge, 1)
+        .get_id::<BadgeObject>(NanoKind::Badge, 1)
         .await
         .expect("Couldn't get by ID an example Badge");
 
@@ -133,3 +194,186 @@ dbg!(client.primary_project(user.data.id).await.unwrap());
 let _ = dbg!(client.pages("pep-talks").await);
 let _ = dbg!(client.pages("nano-prep-101").await);
 */
+
+#[test]
+fn test_project_session_body() {
+    let attributes = ProjectSessionData {
+        count: 500,
+        ..Default::default()
+    };
+    let body =
+        project_session_body(12, 34, attributes).expect("Couldn't build a project session body");
+
+    assert_eq!(
+        body,
+        json!({
+            "data": {
+                "type": "project-sessions",
+                "attributes": {
+                    "count": 500,
+                    "unit-type": 0,
+                },
+                "relationships": {
+                    "project": {
+                        "data": { "id": "12", "type": "projects" }
+                    },
+                    "project-challenge": {
+                        "data": { "id": "34", "type": "project-challenges" }
+                    },
+                },
+            }
+        })
+    );
+}
+
+#[test]
+fn test_check_response_size() {
+    assert!(check_response_size(0, 0).is_ok());
+    assert!(check_response_size(1024, 1024).is_ok());
+    assert!(matches!(
+        check_response_size(1025, 1024),
+        Err(Error::ResponseTooLarge { limit: 1024 })
+    ));
+}
+
+fn genres(ids: std::ops::Range<u64>) -> Vec<GenreObject> {
+    ids.map(|id| GenreObject {
+        id,
+        relationships: None,
+        links: None,
+        attributes: GenreData {
+            name: format!("Genre {id}"),
+            user_id: 1,
+            extra: Default::default(),
+        },
+    })
+    .collect()
+}
+
+fn page_of(data: Vec<GenreObject>) -> CollectionResponse<GenreObject> {
+    CollectionResponse {
+        data,
+        included: None,
+        meta: None,
+        links: None,
+        post_info: None,
+        extra: Default::default(),
+    }
+}
+
+#[tokio::test]
+async fn test_paginate_ignored_pages() {
+    // Every page is the same, as if the endpoint didn't support pagination
+    let fetches = std::cell::Cell::new(0);
+    let items = paginate(|_| {
+        fetches.set(fetches.get() + 1);
+        future::ready(Ok(page_of(genres(1..31))))
+    })
+    .try_collect::<Vec<_>>()
+    .await
+    .unwrap();
+
+    assert_eq!(items.len(), 30);
+    assert_eq!(fetches.get(), 2);
+}
+
+#[tokio::test]
+async fn test_paginate_capped_pages() {
+    // Pages of 10 items instead of the 25 asked for, with the total in the meta
+    let items = paginate(|page| {
+        let start = (page - 1) * 10;
+        let mut resp = page_of(genres(start..(start + 10).min(25)));
+        resp.meta = json!({ "record-count": 25 }).as_object().cloned();
+        future::ready(Ok(resp))
+    })
+    .try_collect::<Vec<_>>()
+    .await
+    .unwrap();
+
+    assert_eq!(items.len(), 25);
+
+    // Pages of 10 items, with a link to the next one until the last
+    let items = paginate(|page| {
+        let mut resp = page_of(genres((page - 1) * 10..page * 10));
+        let next = (page < 3).then(|| format!("/genres?page[number]={}", page + 1));
+        resp.links = json!({ "next": next }).as_object().cloned();
+        future::ready(Ok(resp))
+    })
+    .try_collect::<Vec<_>>()
+    .await
+    .unwrap();
+
+    assert_eq!(items.len(), 30);
+}
+
+#[test]
+fn test_pick_winner_badge() {
+    let data = [
+        (1, "NaNoWriMo Winner", true, true, 2),
+        (2, "Camp NaNoWriMo Winner", true, true, 3),
+        (3, "Old NaNoWriMo Winner", true, false, 1),
+        (4, "Campaign Planner", false, true, 4),
+        (5, "Campfire Storyteller", true, true, 5),
+        (6, "Write Every Day", false, true, 0),
+    ]
+    .into_iter()
+    .map(|(id, title, winner, active, list_order)| {
+        json!({
+            "id": id.to_string(),
+            "type": "badges",
+            "attributes": {
+                "active": active,
+                "adheres-to": "project_challenge",
+                "awarded": "/images/badges/awarded.svg",
+                "awarded-description": "",
+                "badge-type": "word count",
+                "description": title,
+                "generic-description": "",
+                "list-order": list_order,
+                "suborder": null,
+                "title": title,
+                "unawarded": "/images/badges/unawarded.svg",
+                "winner": winner,
+            },
+        })
+    })
+    .collect::<Vec<_>>();
+    let badges: CollectionResponse<BadgeObject> =
+        serde_json::from_str(&json!({ "data": data }).to_string()).expect("Couldn't decode badges");
+
+    let pick = |event_type| {
+        pick_winner_badge(badges.data.clone(), event_type).map(|badge| badge.attributes.title)
+    };
+
+    // "Campfire" isn't "Camp", and inactive badges are left out
+    assert_eq!(
+        pick(EventType::NanoWrimo).as_deref(),
+        Some("NaNoWriMo Winner")
+    );
+    assert_eq!(
+        pick(EventType::CampNano).as_deref(),
+        Some("Camp NaNoWriMo Winner")
+    );
+    assert_eq!(pick(EventType::Custom), None);
+}
+
+#[test]
+fn test_timed_session_data() {
+    // Just after midnight in Auckland is still the day before in UTC
+    let auckland = chrono::FixedOffset::east_opt(13 * 3600).unwrap();
+    let start = auckland.with_ymd_and_hms(2023, 11, 2, 0, 30, 0).unwrap();
+    let end = auckland.with_ymd_and_hms(2023, 11, 2, 1, 0, 0).unwrap();
+
+    let session = timed_session_data(500, start, end);
+
+    assert_eq!(session.count, 500);
+    assert_eq!(session.session_date, NaiveDate::from_ymd_opt(2023, 11, 2));
+    assert_eq!(
+        session.start,
+        Some(Utc.with_ymd_and_hms(2023, 11, 1, 11, 30, 0).unwrap())
+    );
+    assert_eq!(
+        session.end,
+        Some(Utc.with_ymd_and_hms(2023, 11, 1, 12, 0, 0).unwrap())
+    );
+}
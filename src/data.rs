@@ -1,26 +1,51 @@
 use crate::utils::*;
 use crate::{
-    ActionType, AdheresTo, AdminLevel, BadgeType, ContentType, DisplayStatus, EntryMethod,
+    ActionType, AdheresTo, AdminLevel, BadgeType, ContentType, DisplayStatus, EntryMethod, Error,
     EventType, Feeling, GroupType, How, InvitationStatus, JoiningRule, NanoKind, PrivacySetting,
-    ProjectStatus, RegistrationPath, UnitType, Where, WritingType,
+    ProjectStatus, RegistrationPath, UnitType, When, Where, WritingType,
 };
 
 use std::collections::HashMap;
+use std::fmt;
 
 use chrono::{DateTime, NaiveDate, Utc};
 use paste::paste;
+use serde::de::DeserializeOwned;
 use serde::{Deserialize, Serialize};
 
+#[cfg(test)]
+mod tests;
+
 // TODO: May be possible to make time_zone a type from chrono
 
+/// The fields of an object which are unknown to this crate, by their name in the API.
+///
+/// Most structs decoded from the API have an `extra` field of this type. Unknown fields are an
+/// error unless the `lenient` feature is enabled, in which case they are collected there
+/// instead, so it's always empty without the feature. It's there either way so that enabling
+/// the feature doesn't change the structs.
+pub type Extra = HashMap<String, serde_json::Value>;
+
 /// The response of the Nano API when a command results in an expected error
 #[derive(Clone, Serialize, Deserialize, Debug)]
-#[serde(untagged, deny_unknown_fields)]
+#[serde(untagged)]
+#[cfg_attr(not(feature = "lenient"), serde(deny_unknown_fields))]
+#[non_exhaustive]
 pub enum NanoError {
     /// A simple error with just a basic message
-    SimpleError { error: String },
+    SimpleError {
+        error: String,
+        /// Fields unknown to this crate (see [`Extra`])
+        #[serde(default, flatten)]
+        extra: Extra,
+    },
     /// A response with multiple complex errors
-    ErrorList { errors: Vec<ErrorData> },
+    ErrorList {
+        errors: Vec<ErrorData>,
+        /// Fields unknown to this crate (see [`Extra`])
+        #[serde(default, flatten)]
+        extra: Extra,
+    },
 }
 
 #[derive(Clone, Serialize, Deserialize, Debug)]
@@ -35,15 +60,18 @@ pub struct ErrorData {
 
 /// The response from logging into the Nano API
 #[derive(Clone, Serialize, Deserialize, Debug)]
-#[serde(deny_unknown_fields)]
+#[cfg_attr(not(feature = "lenient"), serde(deny_unknown_fields))]
 pub struct LoginResponse {
     /// The authorization token for this log-in session
     pub auth_token: String,
+    /// Fields unknown to this crate (see [`Extra`])
+    #[serde(default, flatten)]
+    pub extra: Extra,
 }
 
 /// Information about Nano's current funraising goals
 #[derive(Clone, Serialize, Deserialize, Debug)]
-#[serde(deny_unknown_fields)]
+#[cfg_attr(not(feature = "lenient"), serde(deny_unknown_fields))]
 pub struct Fundometer {
     /// The current end-goal
     pub goal: u64,
@@ -53,11 +81,28 @@ pub struct Fundometer {
     /// The number of people who have donated
     #[serde(rename = "donorCount")]
     pub donor_count: u64,
+    /// Fields unknown to this crate (see [`Extra`])
+    #[serde(default, flatten)]
+    pub extra: Extra,
+}
+
+/// Headline numbers about Nano as a whole, as returned by
+/// [`NanoClient::site_stats`](crate::NanoClient::site_stats)
+///
+/// The API doesn't expose a site-wide word total, so none is included. More counters may be added
+/// as the API exposes them.
+#[derive(Clone, Serialize, Deserialize, Debug)]
+#[non_exhaustive]
+pub struct SiteStats {
+    /// The number of members of the site-wide group everyone belongs to, if the API reports it
+    pub participants: Option<u64>,
+    /// The current fundraising state
+    pub fundometer: Fundometer,
 }
 
 /// An item from the Nano store
 #[derive(Clone, Serialize, Deserialize, Debug)]
-#[serde(deny_unknown_fields)]
+#[cfg_attr(not(feature = "lenient"), serde(deny_unknown_fields))]
 pub struct StoreItem {
     /// The unique slug for this item
     pub handle: String,
@@ -66,25 +111,181 @@ pub struct StoreItem {
     pub image: String,
     /// The user-facing title of this item
     pub title: String,
+    /// Fields unknown to this crate (see [`Extra`])
+    #[serde(default, flatten)]
+    pub extra: Extra,
+}
+
+/// Aggregate statistics about a region, as returned by
+/// [`NanoClient::region_stats`](crate::NanoClient::region_stats)
+#[derive(Clone, Serialize, Deserialize, Debug, Default)]
+pub struct RegionStats {
+    /// The number of members of the region
+    pub members: u64,
+    /// The number of members taking part in a currently running challenge
+    pub participants: u64,
+    /// The total words written by participants in their currently running challenges
+    pub total_words: u64,
+    /// The average words written per participant
+    pub average_words: f64,
+}
+
+/// An entry of the buddy activity feed, as returned by
+/// [`NanoClient::buddy_activity`](crate::NanoClient::buddy_activity)
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct ActivityItem {
+    /// The buddy who logged the session
+    pub user_id: u64,
+    /// The project the session was logged against
+    pub project_id: u64,
+    /// When the session happened: its end, its start, or when it was logged, whichever is known
+    pub at: DateTime<Utc>,
+    /// The session itself
+    pub session: ProjectSessionObject,
+}
+
+/// An image fetched from the Nano site, such as a project cover or an avatar
+#[derive(Clone, Debug)]
+pub struct Image {
+    /// The raw bytes of the image
+    pub bytes: Vec<u8>,
+    /// The content type of the image, as reported by the server
+    pub content_type: Option<String>,
+}
+
+/// A position in a paginated pull of all the items of a kind, which can be persisted to resume
+/// the pull later (see [`NanoClient::stream_pages`](crate::NanoClient::stream_pages))
+#[derive(Clone, Serialize, Deserialize, Debug, PartialEq, Eq)]
+pub struct PageCursor {
+    /// The kind of items being pulled
+    #[serde(deserialize_with = "de_nanokind", serialize_with = "se_nanokind")]
+    pub kind: NanoKind,
+    /// The filters applied to the items
+    pub filter: Vec<(String, u64)>,
+    /// The next page to fetch, or `None` once all pages have been fetched
+    pub page: Option<u64>,
+}
+
+impl PageCursor {
+    /// Start a pull of all the items of a kind, with filtering to certain related IDs
+    pub fn new(kind: NanoKind, filter: &[(&str, u64)]) -> PageCursor {
+        PageCursor {
+            kind,
+            filter: filter
+                .iter()
+                .map(|&(key, val)| (key.to_string(), val))
+                .collect(),
+            page: Some(1),
+        }
+    }
+
+    /// Whether all pages have been fetched
+    pub fn is_done(&self) -> bool {
+        self.page.is_none()
+    }
+}
+
+/// A unique text identifier of an object, as found in the URLs of the Nano site. Not all kinds
+/// of objects have one.
+///
+/// Slugs are made of letters, digits, `-`, `_` and `.`, and can't be only digits, so that an ID
+/// or a title can't be passed by mistake. Non-ASCII letters are percent-encoded on display.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Slug(String);
+
+impl Slug {
+    /// The slug as it was given
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl TryFrom<&str> for Slug {
+    type Error = Error;
+
+    fn try_from(val: &str) -> Result<Slug, Error> {
+        let valid = !val.is_empty()
+            && !val.chars().all(|c| c.is_ascii_digit())
+            && val
+                .chars()
+                .all(|c| c.is_alphanumeric() || matches!(c, '-' | '_' | '.'));
+
+        if valid {
+            Ok(Slug(val.to_string()))
+        } else {
+            Err(Error::InvalidSlug(val.to_string()))
+        }
+    }
+}
+
+impl fmt::Display for Slug {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for c in self.0.chars() {
+            if c.is_ascii() {
+                write!(f, "{c}")?;
+            } else {
+                let mut buf = [0; 4];
+                for byte in c.encode_utf8(&mut buf).bytes() {
+                    write!(f, "%{byte:02X}")?;
+                }
+            }
+        }
+
+        Ok(())
+    }
 }
 
 /// A successful response from a call to the API which returns multiple items.
 /// Is generic over the inner data type, which allows for the case of a known return type
 /// to avoid needing an unwrap. Defaults to the generic Object
 #[derive(Clone, Serialize, Deserialize, Debug)]
-#[serde(deny_unknown_fields)]
+#[cfg_attr(not(feature = "lenient"), serde(deny_unknown_fields))]
 pub struct CollectionResponse<D: ObjectInfo = Object> {
-    /// The array of returned objects
+    /// The array of returned objects. A single object is also accepted, and treated as an
+    /// array of one, as some endpoints (like relation links) aren't consistent about it
+    #[serde(
+        deserialize_with = "de_one_or_many",
+        bound(deserialize = "D: Deserialize<'de>")
+    )]
     pub data: Vec<D>,
     /// Any included linked objects
     pub included: Option<Vec<Object>>,
+    /// Extra information about the collection, like the total number of matching records, if
+    /// the API provides any
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub meta: Option<serde_json::Map<String, serde_json::Value>>,
+    /// Links to other pages of the collection, like the next one, if the API provides any
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub links: Option<serde_json::Map<String, serde_json::Value>>,
 
     /// Extra info provided for Post objects
     #[serde(flatten)]
     pub post_info: Option<Box<PostInfo>>,
+    /// Fields unknown to this crate (see [`Extra`])
+    #[serde(default, flatten)]
+    pub extra: Extra,
 }
 
 impl<D: ObjectInfo> CollectionResponse<D> {
+    /// The total number of records matching the request, if the API reports it. If this is
+    /// nonzero while the data is empty, the records exist but weren't returned to the current
+    /// user, rather than there being none at all.
+    pub fn record_count(&self) -> Option<u64> {
+        let meta = self.meta.as_ref()?;
+
+        ["record-count", "total", "count"]
+            .iter()
+            .find_map(|key| meta.get(*key)?.as_u64())
+    }
+
+    /// Whether there is a page of the collection after this one, if the API provides pagination
+    /// links to tell
+    pub fn has_next_page(&self) -> Option<bool> {
+        let links = self.links.as_ref()?;
+
+        Some(links.get("next").is_some_and(|next| !next.is_null()))
+    }
+
     /// Find the instance of an ObjectRef in this response's included list, if
     /// an instance exists. Otherwise returns None
     pub fn get_ref(&self, obj_ref: &ObjectRef) -> Option<&Object> {
@@ -95,11 +296,40 @@ impl<D: ObjectInfo> CollectionResponse<D> {
     }
 }
 
+/// Either a single item, or an array of items. Used where the Nano API isn't consistent about
+/// which of the two it returns
+///
+/// Unlike the other enums of this crate, this isn't `non_exhaustive`: there is no third option.
+#[derive(Clone, Serialize, Debug)]
+#[serde(untagged)]
+pub enum OneOrMany<D> {
+    /// An array of items
+    Many(Vec<D>),
+    /// A single item
+    One(D),
+}
+
+impl<D> OneOrMany<D> {
+    /// Get the item(s) as an array, which will have a single item in the [`OneOrMany::One`] case
+    pub fn into_vec(self) -> Vec<D> {
+        match self {
+            OneOrMany::Many(items) => items,
+            OneOrMany::One(item) => vec![item],
+        }
+    }
+}
+
+impl<D> From<OneOrMany<D>> for Vec<D> {
+    fn from(val: OneOrMany<D>) -> Vec<D> {
+        val.into_vec()
+    }
+}
+
 /// A successful response from a call to the API which returns a single item.
 /// Is generic over the inner data type, which allows for the case of a known return type
 /// to avoid needing an unwrap. Defaults to the generic Object
 #[derive(Clone, Serialize, Deserialize, Debug)]
-#[serde(deny_unknown_fields)]
+#[cfg_attr(not(feature = "lenient"), serde(deny_unknown_fields))]
 pub struct ItemResponse<D: ObjectInfo = Object> {
     /// The returned object
     pub data: D,
@@ -110,6 +340,9 @@ pub struct ItemResponse<D: ObjectInfo = Object> {
     /// Extra info provided for Post/Page objects
     #[serde(flatten)]
     pub post_info: Option<Box<PostInfo>>,
+    /// Fields unknown to this crate (see [`Extra`])
+    #[serde(default, flatten)]
+    pub extra: Extra,
 }
 
 impl<D: ObjectInfo> ItemResponse<D> {
@@ -123,9 +356,43 @@ impl<D: ObjectInfo> ItemResponse<D> {
     }
 }
 
-/// The extra info provided when getting a Post/Page object
+/// A snapshot of all of a user's data, as returned by
+/// [`NanoClient::export_user`](crate::NanoClient::export_user)
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct UserExport {
+    /// The user themselves
+    pub user: UserObject,
+    /// All the projects of the user
+    pub projects: Vec<ProjectObject>,
+    /// All the challenges the projects of the user are part of
+    pub project_challenges: Vec<ProjectChallengeObject>,
+    /// All the writing sessions recorded for the projects of the user
+    pub project_sessions: Vec<ProjectSessionObject>,
+    /// All the badges the user has earned
+    pub badges: Vec<UserBadgeObject>,
+}
+
+/// What the current user needs on the front page of an app, fetched all at once, as returned by
+/// [`NanoClient::dashboard`](crate::NanoClient::dashboard)
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct DashboardData {
+    /// The current user
+    pub user: UserObject,
+    /// The projects of the user
+    pub projects: Vec<ProjectObject>,
+    /// The challenges the projects of the user are part of
+    pub project_challenges: Vec<ProjectChallengeObject>,
+    /// The challenges behind those project challenges
+    pub challenges: Vec<ChallengeObject>,
+    /// The notifications of the user
+    pub notifications: Vec<NotificationObject>,
+    /// The groups the user is a member of
+    pub groups: Vec<GroupObject>,
+}
+
+/// The extra info provided when getting a Post/Page object. Its fields are alongside those of the
+/// response, whose `extra` collects any unknown ones.
 #[derive(Clone, Serialize, Deserialize, Debug)]
-#[serde(deny_unknown_fields)]
 pub struct PostInfo {
     /// Posts that come after this one
     pub after_posts: Vec<ItemResponse<PostObject>>,
@@ -137,8 +404,10 @@ pub struct PostInfo {
 
 /// A reference to an included [`Object`]. Declares the kind and ID of the Object,
 /// so that it can be uniquely located in the include list
+///
+/// References are equal when they point to the same Object, whatever their unknown fields.
 #[derive(Clone, Serialize, Deserialize, Debug)]
-#[serde(deny_unknown_fields)]
+#[cfg_attr(not(feature = "lenient"), serde(deny_unknown_fields))]
 pub struct ObjectRef {
     /// The ID of the referenced Object
     #[serde(deserialize_with = "de_str_num", serialize_with = "se_str_id")]
@@ -150,6 +419,24 @@ pub struct ObjectRef {
         serialize_with = "se_nanokind"
     )]
     pub kind: NanoKind,
+    /// Fields unknown to this crate (see [`Extra`])
+    #[serde(default, flatten)]
+    pub extra: Extra,
+}
+
+impl PartialEq for ObjectRef {
+    fn eq(&self, other: &ObjectRef) -> bool {
+        self.id == other.id && self.kind == other.kind
+    }
+}
+
+impl Eq for ObjectRef {}
+
+impl std::hash::Hash for ObjectRef {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.id.hash(state);
+        self.kind.hash(state);
+    }
 }
 
 /// A trait for all types that represent an 'Object' in the Nano API. See [`Object`] for the
@@ -163,6 +450,18 @@ pub trait ObjectInfo: std::fmt::Debug {
     fn relationships(&self) -> &Option<RelationInfo>;
     /// Get the links for this Object, of which there should always be at least a link for `self`
     fn links(&self) -> &Option<LinkInfo>;
+    /// Get the attributes of this Object as JSON, keyed by their name in the API
+    fn attributes_json(&self) -> Result<serde_json::Value, serde_json::Error>;
+
+    /// Decode the attributes of this Object as another type, such as a struct with only the few
+    /// attributes of interest. This works the same for any kind of Object, so is handy to get
+    /// at an attribute common to several kinds without matching on each of them.
+    fn attributes_as<T: DeserializeOwned>(&self) -> Result<T, serde_json::Error>
+    where
+        Self: Sized,
+    {
+        serde_json::from_value(self.attributes_json()?)
+    }
 }
 
 /// A common type for all Nano API objects. Most useful when you're either not sure of an API type,
@@ -170,6 +469,7 @@ pub trait ObjectInfo: std::fmt::Debug {
 /// all these objects have in common
 #[derive(Clone, Serialize, Deserialize, Debug)]
 #[serde(tag = "type")]
+#[non_exhaustive]
 pub enum Object {
     #[serde(rename = "badges")]
     Badge(BadgeObject),
@@ -252,6 +552,30 @@ impl Object {
             Object::UserBadge(data) => data,
         }
     }
+
+    /// A human-readable label for this Object, such as the name of a user or group, or the
+    /// title of a project, for the kinds which have one
+    pub fn display_name(&self) -> Option<&str> {
+        match self {
+            Object::Badge(obj) => Some(&obj.attributes.title),
+            Object::Challenge(obj) => Some(&obj.attributes.name),
+            Object::FavoriteAuthor(obj) => Some(&obj.attributes.name),
+            Object::FavoriteBook(obj) => Some(&obj.attributes.title),
+            Object::Genre(obj) => Some(&obj.attributes.name),
+            Object::Group(obj) => Some(&obj.attributes.name),
+            Object::GroupExternalLink(obj) => obj.attributes.label.as_deref(),
+            Object::Location(obj) => Some(&obj.attributes.name),
+            Object::Notification(obj) => Some(&obj.attributes.headline),
+            Object::Page(obj) => Some(&obj.attributes.headline),
+            Object::Post(obj) => Some(&obj.attributes.headline),
+            Object::Project(obj) => Some(&obj.attributes.title),
+            Object::User(obj) => Some(&obj.attributes.name),
+            Object::WritingLocation(obj) => Some(&obj.attributes.name),
+            Object::WritingMethod(obj) => Some(&obj.attributes.name),
+            Object::ProjectChallenge(obj) => Some(&obj.attributes.name),
+            _ => None,
+        }
+    }
 }
 
 impl ObjectInfo for Object {
@@ -270,6 +594,10 @@ impl ObjectInfo for Object {
     fn links(&self) -> &Option<LinkInfo> {
         self.inner().links()
     }
+
+    fn attributes_json(&self) -> Result<serde_json::Value, serde_json::Error> {
+        self.inner().attributes_json()
+    }
 }
 
 const fn is_zero(n: &u64) -> bool {
@@ -311,6 +639,10 @@ macro_rules! obj_ty {
                 fn links(&self) -> &Option<LinkInfo> {
                     &self.links
                 }
+
+                fn attributes_json(&self) -> Result<serde_json::Value, serde_json::Error> {
+                    serde_json::to_value(&self.attributes)
+                }
             }
 
             impl Object {
@@ -324,6 +656,19 @@ macro_rules! obj_ty {
                     }
                 }
             }
+
+            impl TryFrom<Object> for [<$name Object>] {
+                type Error = Object;
+
+                #[doc = "Take this Object as kind " $name ", or give it back if it's another kind"]
+                fn try_from(obj: Object) -> Result<Self, Object> {
+                    if let Object::$name(inner) = obj {
+                        Ok(inner)
+                    } else {
+                        Err(obj)
+                    }
+                }
+            }
             )+
         }
     }
@@ -358,106 +703,270 @@ obj_ty!(
 );
 
 #[derive(Clone, Serialize, Deserialize, Debug)]
-#[serde(rename_all = "kebab-case", deny_unknown_fields)]
+#[serde(rename_all = "kebab-case")]
+#[cfg_attr(not(feature = "lenient"), serde(deny_unknown_fields))]
 pub struct BadgeData {
+    /// Whether the badge can currently be earned
     pub active: bool,
+    #[serde(alias = "adheres_to")]
     pub adheres_to: AdheresTo,
+    /// The image of the badge once earned
     pub awarded: String,
+    /// The text shown to a user who earned the badge
+    #[serde(alias = "awarded_description")]
     pub awarded_description: String,
+    #[serde(alias = "badge_type")]
     pub badge_type: BadgeType,
+    /// The text explaining what the badge is for
     pub description: String,
+    /// The text shown to a user who hasn't earned the badge yet
+    #[serde(alias = "generic_description")]
     pub generic_description: String,
+    #[serde(alias = "list_order")]
     pub list_order: u64,
     pub suborder: Option<u64>,
     pub title: String,
+    /// The image of the badge before it's earned
     pub unawarded: String,
     pub winner: bool,
+    /// Fields unknown to this crate (see [`Extra`])
+    #[serde(default, flatten)]
+    pub extra: Extra,
+}
+
+impl BadgeData {
+    /// The text to show for this badge, depending on whether the user has earned it. Falls back
+    /// to the general description if there is no specific text for that state.
+    pub fn description_for(&self, earned: bool) -> &str {
+        let specific = if earned {
+            &self.awarded_description
+        } else {
+            &self.generic_description
+        };
+
+        if specific.trim().is_empty() {
+            &self.description
+        } else {
+            specific
+        }
+    }
 }
 
 /// A challenge (Nano, Camp Nano, or custom).
 /// The Optional fields will generally be populated for Nanos or Camps,
 /// but null for custom challenges. (Warning: This is only mostly, not absolutely, true)
-#[derive(Clone, Serialize, Deserialize, Debug)]
-#[serde(rename_all = "kebab-case", deny_unknown_fields)]
+#[derive(Clone, Serialize, Deserialize, Debug, Default)]
+#[serde(rename_all = "kebab-case")]
+#[cfg_attr(not(feature = "lenient"), serde(deny_unknown_fields))]
 pub struct ChallengeData {
+    #[serde(alias = "default_goal")]
     pub default_goal: u64,
+    #[serde(alias = "ends_at")]
     pub ends_at: NaiveDate,
+    #[serde(alias = "event_type")]
     pub event_type: Option<EventType>,
+    #[serde(alias = "flexible_goal")]
     pub flexible_goal: Option<bool>,
     pub name: String,
+    #[serde(alias = "prep_starts_at")]
     pub prep_starts_at: Option<NaiveDate>,
+    #[serde(alias = "starts_at")]
     pub starts_at: NaiveDate,
+    #[serde(alias = "unit_type")]
     pub unit_type: UnitType,
+    #[serde(alias = "user_id")]
     pub user_id: u64,
+    #[serde(alias = "win_allowed_at")]
     pub win_allowed_at: Option<NaiveDate>,
+    #[serde(alias = "writing_type")]
     pub writing_type: WritingType,
+    /// Fields unknown to this crate (see [`Extra`])
+    #[serde(default, flatten)]
+    pub extra: Extra,
 }
 
 #[derive(Clone, Serialize, Deserialize, Debug)]
-#[serde(rename_all = "kebab-case", deny_unknown_fields)]
+#[serde(rename_all = "kebab-case")]
+#[cfg_attr(not(feature = "lenient"), serde(deny_unknown_fields))]
 pub struct DailyAggregateData {
     pub count: u64,
     pub day: NaiveDate,
+    #[serde(alias = "project_id")]
     pub project_id: u64,
+    #[serde(alias = "unit_type")]
     pub unit_type: UnitType,
+    #[serde(alias = "user_id")]
     pub user_id: Option<u64>,
+    /// Fields unknown to this crate (see [`Extra`])
+    #[serde(default, flatten)]
+    pub extra: Extra,
+}
+
+/// A writing streak, computed from the daily aggregates of a project challenge
+#[derive(Clone, Serialize, Deserialize, Debug, Default, PartialEq, Eq)]
+pub struct StreakInfo {
+    /// The number of consecutive days with some writing, up to today. If nothing was written
+    /// yet today, the streak is still considered current if something was written yesterday
+    pub current: u64,
+    /// The largest number of consecutive days with some writing
+    pub longest: u64,
+    /// All the days with some writing, in chronological order
+    pub days: Vec<NaiveDate>,
+}
+
+impl StreakInfo {
+    /// Compute the streak from a set of daily aggregates, as of a given day
+    pub fn from_aggregates(aggregates: &[DailyAggregateObject], today: NaiveDate) -> StreakInfo {
+        let mut days = aggregates
+            .iter()
+            .filter(|agg| agg.attributes.count > 0 && agg.attributes.day <= today)
+            .map(|agg| agg.attributes.day)
+            .collect::<Vec<_>>();
+        days.sort();
+        days.dedup();
+
+        let mut longest = 0;
+        let mut run = 0;
+        let mut last: Option<NaiveDate> = None;
+        for &day in &days {
+            run = match last {
+                Some(last) if day.pred_opt() == Some(last) => run + 1,
+                _ => 1,
+            };
+            longest = longest.max(run);
+            last = Some(day);
+        }
+
+        let current = match last {
+            Some(last) if last == today || last.succ_opt() == Some(today) => run,
+            _ => 0,
+        };
+
+        StreakInfo {
+            current,
+            longest,
+            days,
+        }
+    }
 }
 
 #[derive(Clone, Serialize, Deserialize, Debug)]
-#[serde(rename_all = "kebab-case", deny_unknown_fields)]
+#[serde(rename_all = "kebab-case")]
+#[cfg_attr(not(feature = "lenient"), serde(deny_unknown_fields))]
 pub struct FavoriteAuthorData {
     pub name: String,
+    #[serde(alias = "user_id")]
     pub user_id: u64,
+    /// Fields unknown to this crate (see [`Extra`])
+    #[serde(default, flatten)]
+    pub extra: Extra,
 }
 
 #[derive(Clone, Serialize, Deserialize, Debug)]
-#[serde(rename_all = "kebab-case", deny_unknown_fields)]
+#[serde(rename_all = "kebab-case")]
+#[cfg_attr(not(feature = "lenient"), serde(deny_unknown_fields))]
 pub struct FavoriteBookData {
     pub title: String,
+    #[serde(alias = "user_id")]
     pub user_id: u64,
+    /// Fields unknown to this crate (see [`Extra`])
+    #[serde(default, flatten)]
+    pub extra: Extra,
 }
 
 #[derive(Clone, Serialize, Deserialize, Debug)]
-#[serde(rename_all = "kebab-case", deny_unknown_fields)]
+#[serde(rename_all = "kebab-case")]
+#[cfg_attr(not(feature = "lenient"), serde(deny_unknown_fields))]
 pub struct GenreData {
     pub name: String,
     /// The user who created this Genre label
+    #[serde(alias = "user_id")]
     pub user_id: u64,
+    /// Fields unknown to this crate (see [`Extra`])
+    #[serde(default, flatten)]
+    pub extra: Extra,
 }
 
-#[derive(Clone, Serialize, Deserialize, Debug)]
-#[serde(rename_all = "kebab-case", deny_unknown_fields)]
+#[derive(Clone, Serialize, Deserialize, Debug, Default)]
+#[serde(rename_all = "kebab-case")]
+#[cfg_attr(not(feature = "lenient"), serde(deny_unknown_fields))]
 pub struct GroupData {
+    #[serde(alias = "approved_by_id")]
     pub approved_by_id: u64,
+    #[serde(default, deserialize_with = "de_opt_image_url")]
     pub avatar: Option<String>,
+    #[serde(alias = "cancelled_by_id")]
     pub cancelled_by_id: u64,
+    #[serde(alias = "created_at")]
     pub created_at: DateTime<Utc>,
     pub description: Option<String>,
+    #[serde(alias = "end_dt")]
     pub end_dt: Option<DateTime<Utc>>,
+    #[serde(alias = "forum_link")]
     pub forum_link: Option<String>,
+    #[serde(alias = "group_id")]
     pub group_id: Option<u64>,
+    #[serde(alias = "group_type")]
     pub group_type: GroupType,
+    #[serde(alias = "joining_rule")]
     pub joining_rule: Option<JoiningRule>,
     pub latitude: Option<f64>,
     pub longitude: Option<f64>,
+    #[serde(alias = "max_member_count")]
     pub max_member_count: Option<u64>,
+    #[serde(alias = "member_count")]
     pub member_count: Option<u64>,
     pub name: String,
+    #[serde(default, deserialize_with = "de_opt_image_url")]
     pub plate: Option<String>,
     pub slug: String,
+    #[serde(alias = "start_dt")]
     pub start_dt: Option<DateTime<Utc>>,
+    #[serde(alias = "time_zone")]
     pub time_zone: Option<String>,
+    #[serde(alias = "updated_at")]
     pub updated_at: DateTime<Utc>,
     pub url: Option<String>,
+    #[serde(alias = "user_id")]
     pub user_id: Option<u64>,
+    /// Fields unknown to this crate (see [`Extra`])
+    #[serde(default, flatten)]
+    pub extra: Extra,
+}
+
+impl GroupData {
+    /// The link to the forum of this group, if it's set to a valid web URL
+    #[cfg(feature = "url")]
+    pub fn forum_link(&self) -> Option<url::Url> {
+        parse_web_url(self.forum_link.as_deref()?)
+    }
+
+    /// The link to the website of this group, if it's set to a valid web URL
+    #[cfg(feature = "url")]
+    pub fn website_link(&self) -> Option<url::Url> {
+        parse_web_url(self.url.as_deref()?)
+    }
 }
 
 #[derive(Clone, Serialize, Deserialize, Debug)]
-#[serde(rename_all = "kebab-case", deny_unknown_fields)]
+#[serde(rename_all = "kebab-case")]
+#[cfg_attr(not(feature = "lenient"), serde(deny_unknown_fields))]
 pub struct GroupExternalLinkData {
+    #[serde(alias = "group_id")]
     pub group_id: u64,
     pub label: Option<String>,
     pub url: String,
+    /// Fields unknown to this crate (see [`Extra`])
+    #[serde(default, flatten)]
+    pub extra: Extra,
+}
+
+impl GroupExternalLinkData {
+    /// The link, if it's a valid web URL
+    #[cfg(feature = "url")]
+    pub fn link(&self) -> Option<url::Url> {
+        parse_web_url(&self.url)
+    }
 }
 
 #[derive(Clone, Serialize, Deserialize, Debug)]
@@ -466,128 +975,325 @@ pub struct LocationData {
     pub city: String,
     pub country: String,
     pub county: Option<String>,
+    #[serde(alias = "formatted_address")]
     pub formatted_address: Option<String>,
     pub latitude: f64,
     pub longitude: f64,
+    #[serde(alias = "map_url")]
     pub map_url: Option<String>,
     pub municipality: Option<String>,
     pub name: String,
     pub neighborhood: Option<String>,
     #[serde(deserialize_with = "de_opt_str_num")]
+    #[serde(alias = "postal_code")]
     pub postal_code: Option<u64>,
     pub state: String,
     #[serde(rename = "street1")]
     pub street1: Option<String>,
     #[serde(rename = "street2")]
     pub street2: Option<String>,
+    #[serde(alias = "utc_offset")]
     pub utc_offset: Option<i64>,
 }
 
-#[derive(Clone, Serialize, Deserialize, Debug)]
-#[serde(rename_all = "kebab-case", deny_unknown_fields)]
+#[derive(Clone, Serialize, Deserialize, Debug, Default)]
+#[serde(rename_all = "kebab-case")]
+#[cfg_attr(not(feature = "lenient"), serde(deny_unknown_fields))]
 pub struct NanoMessageData {
     pub content: String,
+    #[serde(alias = "created_at")]
     pub created_at: DateTime<Utc>,
+    #[serde(alias = "group_id")]
     pub group_id: u64,
     pub official: bool,
+    #[serde(alias = "send_email")]
     pub send_email: Option<bool>,
+    #[serde(alias = "sender_avatar_url")]
     pub sender_avatar_url: Option<String>,
+    #[serde(alias = "sender_name")]
     pub sender_name: Option<String>,
+    #[serde(alias = "sender_slug")]
     pub sender_slug: Option<String>,
+    #[serde(alias = "updated_at")]
     pub updated_at: DateTime<Utc>,
+    #[serde(alias = "user_id")]
     pub user_id: u64,
+    /// Fields unknown to this crate (see [`Extra`])
+    #[serde(default, flatten)]
+    pub extra: Extra,
 }
 
 #[derive(Clone, Serialize, Deserialize, Debug)]
-#[serde(rename_all = "kebab-case", deny_unknown_fields)]
+#[serde(rename_all = "kebab-case")]
+#[cfg_attr(not(feature = "lenient"), serde(deny_unknown_fields))]
 pub struct NotificationData {
+    #[serde(alias = "action_id")]
     pub action_id: Option<u64>,
+    #[serde(alias = "action_type")]
     pub action_type: ActionType,
     pub content: String,
+    #[serde(alias = "created_at")]
     pub created_at: DateTime<Utc>,
+    #[serde(alias = "data_count")]
     pub data_count: Option<u64>,
+    #[serde(alias = "display_at")]
     pub display_at: DateTime<Utc>,
+    #[serde(alias = "display_status")]
     pub display_status: DisplayStatus,
     pub headline: String,
+    #[serde(alias = "image_url")]
     pub image_url: Option<String>,
+    #[serde(alias = "last_viewed_at")]
     pub last_viewed_at: Option<DateTime<Utc>>,
+    #[serde(alias = "redirect_url")]
     pub redirect_url: Option<String>,
+    #[serde(alias = "updated_at")]
     pub updated_at: DateTime<Utc>,
+    #[serde(alias = "user_id")]
     pub user_id: u64,
+    /// Fields unknown to this crate (see [`Extra`])
+    #[serde(default, flatten)]
+    pub extra: Extra,
+}
+
+impl NotificationData {
+    /// The API object the redirect URL of this notification points at, if it points at one.
+    /// This looks for the last `{kind}/{id}` pair in the URL's path, so for example
+    /// `/projects/1234/project-challenges/5678` resolves to the project challenge with ID 5678.
+    pub fn redirect_target(&self) -> Option<(NanoKind, u64)> {
+        let url = self.redirect_url.as_deref()?;
+
+        let path = match url.split_once("://") {
+            Some((_, rest)) => rest.split_once('/').map_or("", |(_, path)| path),
+            None => url,
+        };
+        let path = path.split(['?', '#']).next().unwrap_or_default();
+
+        let segments = path
+            .split('/')
+            .filter(|segment| !segment.is_empty())
+            .collect::<Vec<_>>();
+
+        segments.windows(2).rev().find_map(|pair| {
+            let kind = NanoKind::from_name(pair[0]).ok()?;
+            let id = pair[1].parse().ok()?;
+            Some((kind, id))
+        })
+    }
 }
 
 #[derive(Clone, Serialize, Deserialize, Debug)]
-#[serde(rename_all = "kebab-case", deny_unknown_fields)]
+#[serde(rename_all = "kebab-case")]
+#[cfg_attr(not(feature = "lenient"), serde(deny_unknown_fields))]
 pub struct PageData {
     pub body: String,
     pub url: String,
     pub headline: String,
+    #[serde(alias = "content_type")]
     pub content_type: ContentType,
+    #[serde(alias = "show_after")]
     pub show_after: Option<DateTime<Utc>>,
+    #[serde(alias = "promotional_card_image")]
     pub promotional_card_image: Option<String>,
+    /// Fields unknown to this crate (see [`Extra`])
+    #[serde(default, flatten)]
+    pub extra: Extra,
+}
+
+impl PageData {
+    /// The body of this page as plain text, with the markup stripped out. Suitable for display
+    /// in a terminal, or for screen readers.
+    pub fn plain_text(&self) -> String {
+        strip_markup(&self.body)
+    }
+
+    /// Split the body of this page into the pep talks it contains, one per heading. The author
+    /// of a talk is found from a line starting with "by" right under its heading.
+    pub fn pep_talks(&self) -> Vec<PepTalk> {
+        split_headings(&self.body)
+            .into_iter()
+            .map(|(title, body)| {
+                let author = strip_markup(body)
+                    .lines()
+                    .find(|line| !line.trim().is_empty())
+                    .and_then(|line| {
+                        let (by, name) = line.trim().split_at_checked(3)?;
+                        by.eq_ignore_ascii_case("by ")
+                            .then(|| name.trim().to_string())
+                    });
+
+                PepTalk {
+                    title,
+                    author,
+                    body: body.trim().to_string(),
+                }
+            })
+            .collect()
+    }
+}
+
+/// A single pep talk, as found on the pep talks page (see
+/// [`NanoClient::pep_talks`](crate::NanoClient::pep_talks))
+#[derive(Clone, Serialize, Deserialize, Debug, PartialEq, Eq)]
+pub struct PepTalk {
+    /// The title of the talk
+    pub title: String,
+    /// Who wrote the talk, if the page says
+    pub author: Option<String>,
+    /// The talk itself, as HTML
+    pub body: String,
+}
+
+impl PepTalk {
+    /// The body of this talk as plain text, with the markup stripped out
+    pub fn plain_text(&self) -> String {
+        strip_markup(&self.body)
+    }
 }
 
 #[derive(Clone, Serialize, Deserialize, Debug)]
-#[serde(rename_all = "kebab-case", deny_unknown_fields)]
+#[serde(rename_all = "kebab-case")]
+#[cfg_attr(not(feature = "lenient"), serde(deny_unknown_fields))]
 pub struct PostData {
+    #[serde(alias = "api_code")]
     pub api_code: Option<String>, // TODO: ???
     pub body: String,
+    #[serde(alias = "card_image")]
     pub card_image: Option<String>,
+    #[serde(alias = "content_type")]
     pub content_type: ContentType,
+    #[serde(alias = "expires_at")]
     pub expires_at: Option<NaiveDate>,
+    #[serde(alias = "external_link")]
     pub external_link: Option<String>,
     pub headline: String,
+    #[serde(alias = "offer_code")]
     pub offer_code: Option<String>,
     pub order: Option<u64>,
     pub published: bool,
     pub subhead: Option<String>, // TODO: ???
+    /// Fields unknown to this crate (see [`Extra`])
+    #[serde(default, flatten)]
+    pub extra: Extra,
 }
 
-#[derive(Clone, Serialize, Deserialize, Debug)]
-#[serde(rename_all = "kebab-case", deny_unknown_fields)]
+#[derive(Clone, Serialize, Deserialize, Debug, Default)]
+#[serde(rename_all = "kebab-case")]
+#[cfg_attr(not(feature = "lenient"), serde(deny_unknown_fields))]
 pub struct ProjectData {
     pub cover: Option<String>,
+    #[serde(alias = "created_at")]
     pub created_at: DateTime<Utc>,
     pub excerpt: Option<String>,
+    #[serde(alias = "pinterest_url")]
     pub pinterest_url: Option<String>,
+    #[serde(alias = "playlist_url")]
     pub playlist_url: Option<String>,
-    pub primary: Option<i64>, // TODO: Figure out what this means. It's not an enum
+    /// Whether this is the user's primary project. Sent as an integer by the API, where any
+    /// non-zero value is taken to mean primary.
+    #[serde(
+        default,
+        deserialize_with = "de_opt_bool_or_int",
+        serialize_with = "se_opt_bool_int"
+    )]
+    pub primary: Option<bool>,
     pub privacy: PrivacySetting,
     pub slug: String,
     pub status: ProjectStatus,
     pub summary: Option<String>,
     pub title: String,
+    #[serde(alias = "unit_count")]
     pub unit_count: Option<u64>,
+    #[serde(alias = "unit_type")]
     pub unit_type: UnitType,
+    #[serde(alias = "user_id")]
     pub user_id: u64,
+    #[serde(alias = "writing_type")]
     pub writing_type: WritingType,
+    /// Fields unknown to this crate (see [`Extra`])
+    #[serde(default, flatten)]
+    pub extra: Extra,
+}
+
+impl ProjectData {
+    /// The link to the playlist of this project, if it's set to a valid web URL
+    #[cfg(feature = "url")]
+    pub fn playlist_link(&self) -> Option<url::Url> {
+        parse_web_url(self.playlist_url.as_deref()?)
+    }
+
+    /// The link to the Pinterest board of this project, if it's set to a valid web URL
+    #[cfg(feature = "url")]
+    pub fn pinterest_link(&self) -> Option<url::Url> {
+        parse_web_url(self.pinterest_url.as_deref()?)
+    }
 }
 
 #[derive(Clone, Serialize, Deserialize, Debug, Default)]
-#[serde(rename_all = "kebab-case", deny_unknown_fields)]
+#[serde(rename_all = "kebab-case")]
+#[cfg_attr(not(feature = "lenient"), serde(deny_unknown_fields))]
 pub struct ProjectSessionData {
     pub count: i64,
+    #[serde(alias = "created_at")]
     pub created_at: Option<DateTime<Utc>>,
     pub end: Option<DateTime<Utc>>,
     pub feeling: Option<Feeling>,
     pub how: Option<How>,
+    #[serde(alias = "project_challenge_id")]
     pub project_challenge_id: Option<u64>,
+    #[serde(alias = "project_id")]
     pub project_id: Option<u64>,
+    #[serde(
+        alias = "session_date",
+        default,
+        deserialize_with = "de_opt_flexible_date"
+    )]
     pub session_date: Option<NaiveDate>,
     pub start: Option<DateTime<Utc>>,
+    #[serde(alias = "unit_type")]
     pub unit_type: UnitType,
     pub r#where: Option<Where>,
+    /// Fields unknown to this crate (see [`Extra`])
+    #[serde(default, flatten)]
+    pub extra: Extra,
+}
+
+impl ProjectSessionData {
+    /// How long this session lasted, if both its start and end are known
+    pub fn session_duration(&self) -> Option<chrono::Duration> {
+        Some(self.end? - self.start?)
+    }
+
+    /// The writing speed during this session, in words per minute. Only available for sessions
+    /// counted in words, with a known start and end.
+    pub fn words_per_minute(&self) -> Option<f64> {
+        if self.unit_type != UnitType::Words {
+            return None;
+        }
+
+        let seconds = self.session_duration()?.num_seconds();
+        if seconds <= 0 {
+            return None;
+        }
+
+        Some(self.count as f64 * 60.0 / seconds as f64)
+    }
 }
 
 #[derive(Clone, Serialize, Deserialize, Debug)]
-#[serde(rename_all = "kebab-case", deny_unknown_fields)]
+#[serde(rename_all = "kebab-case")]
+#[cfg_attr(not(feature = "lenient"), serde(deny_unknown_fields))]
 pub struct StopWatchData {
     pub start: DateTime<Utc>,
     pub stop: Option<DateTime<Utc>>,
+    /// Fields unknown to this crate (see [`Extra`])
+    #[serde(default, flatten)]
+    pub extra: Extra,
 }
 
 #[derive(Clone, Serialize, Deserialize, Debug)]
-#[serde(rename_all = "kebab-case", deny_unknown_fields)]
+#[serde(rename_all = "kebab-case")]
+#[cfg_attr(not(feature = "lenient"), serde(deny_unknown_fields))]
 pub struct TimerData {
     pub cancelled: bool,
     #[serde(
@@ -596,23 +1302,35 @@ pub struct TimerData {
     )]
     pub duration: chrono::Duration,
     pub start: DateTime<Utc>,
+    /// Fields unknown to this crate (see [`Extra`])
+    #[serde(default, flatten)]
+    pub extra: Extra,
 }
 
 #[derive(Clone, Serialize, Deserialize, Debug)]
 #[serde(rename_all = "kebab-case")]
 pub struct UserData {
+    #[serde(alias = "admin_level")]
     pub admin_level: AdminLevel,
+    #[serde(default, deserialize_with = "de_opt_image_url")]
     pub avatar: Option<String>,
     pub bio: Option<String>,
+    #[serde(alias = "confirmed_at")]
     pub confirmed_at: DateTime<Utc>,
+    #[serde(alias = "created_at")]
     pub created_at: DateTime<Utc>,
+    #[serde(alias = "discourse_username")]
     pub discourse_username: Option<String>,
     pub email: Option<String>,
 
     #[serde(flatten)]
     pub email_settings: Option<EmailSettings>,
 
+    /// Missing for new accounts, in which case it's false
+    #[serde(default)]
     pub halo: bool,
+    /// Missing for new accounts, in which case it's zero
+    #[serde(default)]
     pub laurels: u64,
     pub location: Option<String>,
     pub name: String,
@@ -620,26 +1338,36 @@ pub struct UserData {
     #[serde(flatten)]
     pub notification_settings: Option<NotificationSettings>,
 
+    #[serde(alias = "notifications_viewed_at")]
     pub notifications_viewed_at: DateTime<Utc>,
+    #[serde(default, deserialize_with = "de_opt_image_url")]
     pub plate: Option<String>,
     #[serde(deserialize_with = "de_opt_str_num")]
+    #[serde(alias = "postal_code")]
     pub postal_code: Option<u64>,
 
     #[serde(flatten)]
     pub privacy_settings: Option<PrivacySettings>,
 
+    #[serde(alias = "registration_path")]
     pub registration_path: RegistrationPath,
-    pub setting_session_count_by_session: u8, // TODO: ???
-    pub setting_session_more_info: bool,      // TODO: ???
+    /// Whether word counts are entered for each session, rather than as a new running total.
+    /// The API sends this as 0 or 1, but any other value is tolerated.
+    #[serde(deserialize_with = "de_bool_or_int", serialize_with = "se_bool_int")]
+    #[serde(alias = "setting_session_count_by_session")]
+    pub setting_session_count_by_session: bool,
+    #[serde(alias = "setting_session_more_info")]
+    pub setting_session_more_info: bool, // TODO: ???
     pub slug: String,
 
     #[serde(flatten)]
     pub stats: StatsInfo,
 
+    #[serde(alias = "time_zone")]
     pub time_zone: String,
 }
 
-#[derive(Clone, Serialize, Deserialize, Debug)]
+#[derive(Clone, Serialize, Deserialize, Debug, PartialEq, Eq)]
 pub struct EmailSettings {
     #[serde(rename = "email-blog-posts")]
     pub blog_posts: bool,
@@ -661,7 +1389,7 @@ pub struct EmailSettings {
     pub writing_reminders: bool,
 }
 
-#[derive(Clone, Serialize, Deserialize, Debug)]
+#[derive(Clone, Serialize, Deserialize, Debug, PartialEq, Eq)]
 pub struct NotificationSettings {
     #[serde(rename = "notification-buddy-activities")]
     pub buddy_activities: bool,
@@ -687,7 +1415,7 @@ pub struct NotificationSettings {
     pub writing_reminders: bool,
 }
 
-#[derive(Clone, Serialize, Deserialize, Debug)]
+#[derive(Clone, Serialize, Deserialize, Debug, PartialEq, Eq)]
 pub struct PrivacySettings {
     #[serde(rename = "privacy-send-nanomessages")]
     pub send_nanomessages: PrivacySetting,
@@ -707,6 +1435,57 @@ pub struct PrivacySettings {
     pub visibility_regions: bool,
 }
 
+/// All the settings of a user, apart from their profile, as returned by
+/// [`NanoClient::settings_snapshot`](crate::NanoClient::settings_snapshot)
+#[derive(Clone, Serialize, Deserialize, Debug, PartialEq, Eq)]
+pub struct UserSettings {
+    #[serde(flatten)]
+    pub email: EmailSettings,
+    #[serde(flatten)]
+    pub notifications: NotificationSettings,
+    #[serde(flatten)]
+    pub privacy: PrivacySettings,
+}
+
+impl UserSettings {
+    /// Get the settings of a user, if they are all available. They generally only are for the
+    /// current user.
+    pub fn from_user(user: &UserData) -> Option<UserSettings> {
+        Some(UserSettings {
+            email: user.email_settings.clone()?,
+            notifications: user.notification_settings.clone()?,
+            privacy: user.privacy_settings.clone()?,
+        })
+    }
+
+    /// The settings which differ in `other`, with their new values, keyed by their attribute
+    /// name in the API. This is suitable to send as-is to update only what changed.
+    pub fn diff(&self, other: &UserSettings) -> serde_json::Map<String, serde_json::Value> {
+        let (Ok(serde_json::Value::Object(before)), Ok(serde_json::Value::Object(after))) =
+            (serde_json::to_value(self), serde_json::to_value(other))
+        else {
+            return Default::default();
+        };
+
+        after
+            .into_iter()
+            .filter(|(key, val)| before.get(key) != Some(val))
+            .collect()
+    }
+}
+
+/// Whether the user is reminded to write, by email and by site notification. The API only
+/// exposes these toggles: there is no way to set when the reminders are sent.
+#[derive(Clone, Copy, Serialize, Deserialize, Debug, Default, PartialEq, Eq)]
+pub struct WritingReminders {
+    /// Whether reminders are sent by email
+    #[serde(rename = "email-writing-reminders")]
+    pub email: bool,
+    /// Whether reminders are sent as site notifications
+    #[serde(rename = "notification-writing-reminders")]
+    pub notification: bool,
+}
+
 // TODO: What do these *mean*, are all of them the right type?
 #[derive(Clone, Serialize, Deserialize, Debug)]
 pub struct StatsInfo {
@@ -748,95 +1527,197 @@ pub struct WritingMethodData {
     pub name: String,
 }
 
-#[derive(Clone, Serialize, Deserialize, Debug)]
-#[serde(rename_all = "kebab-case", deny_unknown_fields)]
+#[derive(Clone, Serialize, Deserialize, Debug, Default)]
+#[serde(rename_all = "kebab-case")]
+#[cfg_attr(not(feature = "lenient"), serde(deny_unknown_fields))]
 pub struct GroupUserData {
+    #[serde(alias = "created_at")]
     pub created_at: DateTime<Utc>,
+    #[serde(alias = "entry_at")]
     pub entry_at: Option<DateTime<Utc>>,
+    #[serde(alias = "entry_method")]
     pub entry_method: EntryMethod,
+    #[serde(alias = "exit_at")]
     pub exit_at: Option<DateTime<Utc>>,
+    #[serde(alias = "exit_method")]
     pub exit_method: Option<String>, // TODO: Enum
+    #[serde(alias = "group_code_id")]
     pub group_code_id: Option<u64>,
+    #[serde(alias = "group_id")]
     pub group_id: u64,
+    #[serde(alias = "group_type")]
     pub group_type: GroupType,
+    #[serde(alias = "invitation_accepted")]
     pub invitation_accepted: InvitationStatus,
+    #[serde(alias = "invited_by_id")]
     pub invited_by_id: Option<u64>,
+    #[serde(alias = "is_admin")]
     pub is_admin: Option<bool>,
+    #[serde(alias = "latest_message")]
     pub latest_message: Option<String>,
+    /// Missing or null for groups without messaging, in which case it's zero
+    #[serde(default, deserialize_with = "de_null_default")]
+    #[serde(alias = "num_unread_messages")]
     pub num_unread_messages: u64,
-    pub primary: u64,
+    /// Whether this is the user's primary group of its type, such as their home region. Sent as
+    /// an integer by the API.
+    #[serde(deserialize_with = "de_bool_or_int", serialize_with = "se_bool_int")]
+    pub primary: bool,
+    #[serde(alias = "updated_at")]
     pub updated_at: DateTime<Utc>,
+    #[serde(alias = "user_id")]
     pub user_id: u64,
+    /// Fields unknown to this crate (see [`Extra`])
+    #[serde(default, flatten)]
+    pub extra: Extra,
 }
 
 #[derive(Clone, Serialize, Deserialize, Debug)]
-#[serde(rename_all = "kebab-case", deny_unknown_fields)]
+#[serde(rename_all = "kebab-case")]
+#[cfg_attr(not(feature = "lenient"), serde(deny_unknown_fields))]
 pub struct LocationGroupData {
+    #[serde(alias = "group_id")]
     pub group_id: u64,
+    #[serde(alias = "location_id")]
     pub location_id: u64,
+    /// Whether this is the primary location of the group. Sent as a boolean by the API, but
+    /// integers are tolerated like for the other `primary` flags.
+    #[serde(deserialize_with = "de_bool_or_int")]
     pub primary: bool,
+    /// Fields unknown to this crate (see [`Extra`])
+    #[serde(default, flatten)]
+    pub extra: Extra,
 }
 
-#[derive(Clone, Serialize, Deserialize, Debug)]
-#[serde(rename_all = "kebab-case", deny_unknown_fields)]
+#[derive(Clone, Serialize, Deserialize, Debug, Default)]
+#[serde(rename_all = "kebab-case")]
+#[cfg_attr(not(feature = "lenient"), serde(deny_unknown_fields))]
 pub struct ProjectChallengeData {
+    #[serde(alias = "challenge_id")]
     pub challenge_id: u64,
+    #[serde(alias = "current_count")]
     pub current_count: u64,
+    #[serde(alias = "ends_at")]
     pub ends_at: NaiveDate,
+    #[serde(alias = "event_type")]
     pub event_type: EventType,
     pub feeling: Option<Feeling>,
     pub goal: u64,
     pub how: Option<How>,
+    #[serde(alias = "last_recompute")]
     pub last_recompute: Option<DateTime<Utc>>,
     pub name: String,
+    #[serde(alias = "project_id")]
     pub project_id: u64,
-    pub speed: Option<u64>, // TODO: ???
+    /// The writing pace the user reports for this challenge. Unconfirmed: this is thought to be
+    /// in words per minute, but hasn't been checked against a real response.
+    pub speed: Option<u64>,
+    #[serde(alias = "start_count")]
     pub start_count: Option<u64>,
+    #[serde(alias = "starts_at")]
     pub starts_at: NaiveDate,
     pub streak: Option<u64>,
+    #[serde(alias = "unit_type")]
     pub unit_type: UnitType,
+    #[serde(alias = "user_id")]
     pub user_id: u64,
-    pub when: Option<u64>, // TODO: ???
+    pub when: Option<When>,
+    #[serde(alias = "won_at")]
     pub won_at: Option<DateTime<Utc>>,
+    #[serde(alias = "writing_location")]
     pub writing_location: Option<String>, // TODO: ???
+    #[serde(alias = "writing_type")]
     pub writing_type: Option<WritingType>,
+    /// Fields unknown to this crate (see [`Extra`])
+    #[serde(default, flatten)]
+    pub extra: Extra,
+}
+
+impl ProjectChallengeData {
+    /// Whether the challenge is running on a given day, that is whether the day falls between
+    /// its start and end (inclusive)
+    pub fn is_running_on(&self, day: NaiveDate) -> bool {
+        self.starts_at <= day && day <= self.ends_at
+    }
+
+    /// Whether the goal of this challenge has been reached: either the server recorded a win,
+    /// or the count already crossed the goal and the win just hasn't been recomputed yet
+    pub fn has_won(&self) -> bool {
+        self.won_at.is_some() || self.current_count >= self.goal
+    }
+
+    /// The next milestone of this challenge not yet reached, out of 25%, 50%, 75%, and 100% of
+    /// the goal, along with the day it would be reached when writing at a steady pace from the
+    /// start of the challenge to its end. Returns None once the goal is reached.
+    pub fn next_milestone(&self) -> Option<(NaiveDate, u64)> {
+        if self.goal == 0 || self.ends_at < self.starts_at {
+            return None;
+        }
+        let days = (self.ends_at - self.starts_at).num_days() as u64 + 1;
+
+        let target = [1, 2, 3, 4]
+            .into_iter()
+            .map(|quarter| (self.goal * quarter).div_ceil(4))
+            .find(|&target| target > self.current_count)?;
+
+        // The first day on which the steady pace total reaches the target
+        let day = (target * days).div_ceil(self.goal);
+        let date = self.starts_at + chrono::Duration::days(day as i64 - 1);
+
+        Some((date, target))
+    }
 }
 
 #[derive(Clone, Serialize, Deserialize, Debug)]
-#[serde(rename_all = "kebab-case", deny_unknown_fields)]
+#[serde(rename_all = "kebab-case")]
+#[cfg_attr(not(feature = "lenient"), serde(deny_unknown_fields))]
 pub struct UserBadgeData {
+    #[serde(alias = "badge_id")]
     pub badge_id: u64,
+    #[serde(alias = "created_at")]
     pub created_at: DateTime<Utc>,
+    #[serde(alias = "project_challenge_id")]
     pub project_challenge_id: u64,
+    #[serde(alias = "user_id")]
     pub user_id: u64,
+    /// Fields unknown to this crate (see [`Extra`])
+    #[serde(default, flatten)]
+    pub extra: Extra,
 }
 
-// This doesn't like deny_unknown_fields, due to flatten
-#[derive(Clone, Serialize, Deserialize, Debug)]
+/// The relationships of an object: links to fetch related objects, and references to related
+/// objects present in the included items. Both are sent in the same object by the API, and are
+/// split apart here.
+#[derive(Clone, Debug)]
 pub struct RelationInfo {
     /// If this is Some, all references are included in the response Include array
-    #[serde(
-        flatten,
-        deserialize_with = "de_rel_includes",
-        serialize_with = "se_rel_includes"
-    )]
+    ///
+    /// A relation sent with `"data": null`, such as a to-one relation which isn't set, has no
+    /// entry here, the same as one sent without data. It comes back out without data.
     pub included: HashMap<NanoKind, Vec<ObjectRef>>,
-    #[serde(
-        flatten,
-        deserialize_with = "de_relation",
-        serialize_with = "se_relation"
-    )]
     pub relations: HashMap<NanoKind, RelationLink>,
 }
 
 #[derive(Clone, Serialize, Deserialize, Debug)]
-#[serde(deny_unknown_fields)]
+#[cfg_attr(not(feature = "lenient"), serde(deny_unknown_fields))]
 pub struct RelationLink {
     #[serde(rename = "self")]
     pub this: String,
     pub related: String,
 }
 
+impl RelationLink {
+    /// Whether this relation ties to a single object, rather than to many, going by the name of
+    /// the relation at the end of the link (see [`NanoKind::is_singleton_relation`]). Returns
+    /// None if the relation isn't of a known kind.
+    pub fn is_singleton(&self) -> Option<bool> {
+        let path = self.related.split(['?', '#']).next().unwrap_or_default();
+        let name = path.trim_end_matches('/').rsplit('/').next()?;
+
+        NanoKind::is_singleton_relation(name)
+    }
+}
+
 #[derive(Clone, Serialize, Deserialize, Debug)]
 pub struct LinkInfo {
     #[serde(rename = "self")]
@@ -0,0 +1,893 @@
+use super::*;
+use proptest::prelude::*;
+use serde_json::json;
+use std::collections::HashSet;
+
+const GENRE: &str =
+    r#"{"id": "3", "type": "genres", "attributes": {"name": "Fantasy", "user-id": 1}}"#;
+
+#[test]
+fn test_collection_one_or_many() {
+    let many: CollectionResponse<GenreObject> = serde_json::from_str(&format!(
+        r#"{{"data": [{GENRE}, {GENRE}], "included": null}}"#
+    ))
+    .expect("Couldn't decode a collection from an array");
+    assert_eq!(many.data.len(), 2);
+
+    let one: CollectionResponse<GenreObject> =
+        serde_json::from_str(&format!(r#"{{"data": {GENRE}, "included": null}}"#))
+            .expect("Couldn't decode a collection from a single object");
+    assert_eq!(one.data.len(), 1);
+    assert_eq!(one.data[0].attributes.name, "Fantasy");
+}
+
+fn aggregate(day: NaiveDate, count: u64) -> DailyAggregateObject {
+    DailyAggregateObject {
+        id: 0,
+        relationships: None,
+        links: None,
+        attributes: DailyAggregateData {
+            count,
+            day,
+            project_id: 1,
+            unit_type: UnitType::Words,
+            user_id: None,
+            extra: Default::default(),
+        },
+    }
+}
+
+#[test]
+fn test_streak_from_aggregates() {
+    let day = |d| NaiveDate::from_ymd_opt(2023, 11, d).unwrap();
+    let aggregates = [
+        aggregate(day(1), 1667),
+        aggregate(day(2), 2000),
+        aggregate(day(3), 500),
+        aggregate(day(4), 0),
+        aggregate(day(5), 1000),
+        aggregate(day(6), 1200),
+    ];
+
+    let streak = StreakInfo::from_aggregates(&aggregates, day(7));
+    assert_eq!(streak.current, 2);
+    assert_eq!(streak.longest, 3);
+    assert_eq!(streak.days.len(), 5);
+
+    let streak = StreakInfo::from_aggregates(&aggregates, day(8));
+    assert_eq!(streak.current, 0);
+    assert_eq!(streak.longest, 3);
+}
+
+#[test]
+fn test_bool_or_int() {
+    #[derive(Deserialize)]
+    struct Flag(#[serde(deserialize_with = "de_bool_or_int")] bool);
+
+    for (raw, expected) in [
+        ("true", true),
+        ("false", false),
+        ("0", false),
+        ("1", true),
+        ("2", true),
+    ] {
+        let Flag(val) = serde_json::from_str(raw).unwrap();
+        assert_eq!(val, expected, "{raw} didn't decode as {expected}");
+    }
+}
+
+#[test]
+fn test_notification_redirect_target() {
+    let notification = |url: &str| NotificationData {
+        action_id: None,
+        action_type: ActionType::ProjectsPage,
+        content: String::new(),
+        created_at: Utc::now(),
+        data_count: None,
+        display_at: Utc::now(),
+        display_status: DisplayStatus::AllNotifs,
+        headline: String::new(),
+        image_url: None,
+        last_viewed_at: None,
+        redirect_url: Some(url.into()),
+        updated_at: Utc::now(),
+        user_id: 1,
+        extra: Default::default(),
+    };
+
+    assert_eq!(
+        notification("https://nanowrimo.org/projects/1234/project-challenges/5678?tab=stats")
+            .redirect_target(),
+        Some((NanoKind::ProjectChallenge, 5678))
+    );
+    assert_eq!(
+        notification("/badges/12").redirect_target(),
+        Some((NanoKind::Badge, 12))
+    );
+    assert_eq!(
+        notification("/participants/some-slug/buddies").redirect_target(),
+        None
+    );
+}
+
+#[test]
+fn test_object_try_from() {
+    let obj: Object = serde_json::from_str(GENRE).unwrap();
+
+    let obj = ProjectObject::try_from(obj).expect_err("Genre was converted into a Project");
+    let genre = GenreObject::try_from(obj).expect("Genre couldn't be converted into a Genre");
+    assert_eq!(genre.id, 3);
+}
+
+#[test]
+fn test_session_words_per_minute() {
+    let start = Utc::now();
+    let mut session = ProjectSessionData {
+        count: 1500,
+        start: Some(start),
+        end: Some(start + chrono::Duration::minutes(30)),
+        ..Default::default()
+    };
+
+    assert_eq!(
+        session.session_duration(),
+        Some(chrono::Duration::minutes(30))
+    );
+    assert_eq!(session.words_per_minute(), Some(50.0));
+
+    session.unit_type = UnitType::Hours;
+    assert_eq!(session.words_per_minute(), None);
+}
+
+#[test]
+fn test_page_plain_text() {
+    let page = PageData {
+        body: "<h2>Pep Talk</h2><p>Dear   writer,</p><p>Keep going &amp; don&#39;t stop!<br/>You&#x27;re nearly there.</p>".into(),
+        url: String::new(),
+        headline: String::new(),
+        content_type: ContentType::PepTalk,
+        show_after: None,
+        promotional_card_image: None,
+        extra: Default::default(),
+    };
+
+    assert_eq!(
+        page.plain_text(),
+        "Pep Talk\n\nDear writer,\n\nKeep going & don't stop!\nYou're nearly there."
+    );
+}
+
+#[test]
+fn test_page_pep_talks() {
+    let page = PageData {
+        body: concat!(
+            "<p>Our pep talks for this year:</p>",
+            "<h2>Keep Going</h2><p><em>By Jane Doe</em></p><p>Dear writer, keep going.</p>",
+            "<H3 class=\"talk\">Second <b>Wind</b></H3><p>You can do it!</p>",
+        )
+        .into(),
+        url: String::new(),
+        headline: String::new(),
+        content_type: ContentType::PepTalk,
+        show_after: None,
+        promotional_card_image: None,
+        extra: Default::default(),
+    };
+
+    let talks = page.pep_talks();
+    assert_eq!(talks.len(), 2);
+
+    assert_eq!(talks[0].title, "Keep Going");
+    assert_eq!(talks[0].author.as_deref(), Some("Jane Doe"));
+    assert_eq!(
+        talks[0].plain_text(),
+        "By Jane Doe\n\nDear writer, keep going."
+    );
+
+    assert_eq!(talks[1].title, "Second Wind");
+    assert_eq!(talks[1].author, None);
+    assert_eq!(talks[1].body, "<p>You can do it!</p>");
+}
+
+#[test]
+fn test_snake_case_aliases() {
+    let kebab: DailyAggregateData = serde_json::from_value(json!({
+        "count": 100,
+        "day": "2023-11-01",
+        "project-id": 12,
+        "unit-type": 0,
+        "user-id": 34,
+    }))
+    .unwrap();
+    let snake: DailyAggregateData = serde_json::from_value(json!({
+        "count": 100,
+        "day": "2023-11-01",
+        "project_id": 12,
+        "unit_type": 0,
+        "user_id": 34,
+    }))
+    .unwrap();
+
+    assert_eq!(kebab.project_id, snake.project_id);
+    assert_eq!(kebab.unit_type, snake.unit_type);
+    assert_eq!(kebab.user_id, snake.user_id);
+}
+
+#[cfg(feature = "raw_value")]
+#[test]
+fn test_raw_attribute() {
+    use crate::utils::RawAttributes;
+
+    let raw = format!(r#"{{"data": {GENRE}}}"#);
+    let jd = &mut serde_json::Deserializer::from_str(&raw);
+    let attrs: RawAttributes = serde_path_to_error::deserialize(jd).unwrap();
+
+    assert_eq!(attrs.get::<u64>("user-id").unwrap(), 1);
+    assert_eq!(attrs.get::<String>("name").unwrap(), "Fantasy");
+    assert!(attrs.get::<u64>("current-count").is_err());
+}
+
+#[test]
+fn test_unknown_field() {
+    let raw = r#"{"id": "3", "type": "genres", "attributes": {"name": "Fantasy", "user-id": 1, "shiny": true}}"#;
+    let genre = serde_json::from_str::<GenreObject>(raw);
+
+    #[cfg(not(feature = "lenient"))]
+    assert!(genre.is_err());
+
+    #[cfg(feature = "lenient")]
+    {
+        let genre = genre.expect("Couldn't decode a genre with an unknown field");
+        assert_eq!(genre.attributes.name, "Fantasy");
+        assert_eq!(genre.attributes.extra["shiny"], json!(true));
+    }
+
+    let raw = r#"{"data": [], "jsonapi": {"version": "1.0"}}"#;
+    let resp = serde_json::from_str::<CollectionResponse>(raw);
+
+    #[cfg(not(feature = "lenient"))]
+    assert!(resp.is_err());
+
+    #[cfg(feature = "lenient")]
+    {
+        let resp = resp.expect("Couldn't decode a response with an unknown field");
+        assert_eq!(resp.extra["jsonapi"], json!({"version": "1.0"}));
+    }
+}
+
+#[test]
+fn test_post_info() {
+    let raw = r#"{
+        "data": [],
+        "after_posts": [],
+        "author_cards": {"data": []},
+        "before_posts": []
+    }"#;
+    let resp = serde_json::from_str::<CollectionResponse>(raw)
+        .expect("Couldn't decode a response with post info");
+
+    assert!(resp.post_info.is_some());
+    assert!(resp.extra.is_empty());
+}
+
+#[test]
+fn test_session_date_formats() {
+    let session = |date: serde_json::Value| {
+        serde_json::from_value::<ProjectSessionData>(json!({
+            "count": 100,
+            "session-date": date,
+            "unit-type": 0,
+        }))
+        .expect("Couldn't decode a session")
+        .session_date
+    };
+
+    let day = NaiveDate::from_ymd_opt(2023, 11, 12);
+    assert_eq!(session(json!("2023-11-12")), day);
+    assert_eq!(session(json!("2023-11-12T23:30:00.000-05:00")), day);
+    assert_eq!(session(json!("2023-11-12T08:00:00")), day);
+    assert_eq!(session(json!(null)), None);
+}
+
+#[test]
+fn test_object_ref_dedup() {
+    let refs: Vec<ObjectRef> = serde_json::from_str(
+        r#"[{"id": "1", "type": "users"}, {"id": "1", "type": "projects"}, {"id": "1", "type": "users"}]"#,
+    )
+    .unwrap();
+
+    assert_eq!(refs[0], refs[2]);
+    assert_ne!(refs[0], refs[1]);
+    assert_eq!(refs.into_iter().collect::<HashSet<_>>().len(), 2);
+}
+
+#[test]
+fn test_slug() {
+    let slug = Slug::try_from("nano-prep-101").unwrap();
+    assert_eq!(slug.as_str(), "nano-prep-101");
+    assert_eq!(slug.to_string(), "nano-prep-101");
+
+    assert_eq!(Slug::try_from("café").unwrap().to_string(), "caf%C3%A9");
+
+    for invalid in ["", "2617284", "My Great Novel", "pages/about"] {
+        assert!(matches!(
+            Slug::try_from(invalid),
+            Err(Error::InvalidSlug(slug)) if slug == invalid
+        ));
+    }
+}
+
+#[test]
+fn test_badge_description_for() {
+    let mut badge: BadgeData = serde_json::from_str(
+        &json!({
+            "active": true,
+            "adheres-to": "user",
+            "awarded": "/images/badges/awarded.svg",
+            "awarded-description": "You wrote every day!",
+            "badge-type": "self-awarded",
+            "description": "Write every day",
+            "generic-description": "Can you write every day?",
+            "list-order": 1,
+            "suborder": null,
+            "title": "Daily",
+            "unawarded": "/images/badges/unawarded.svg",
+            "winner": false,
+        })
+        .to_string(),
+    )
+    .expect("Couldn't decode a badge");
+
+    assert_eq!(badge.description_for(true), "You wrote every day!");
+    assert_eq!(badge.description_for(false), "Can you write every day?");
+
+    badge.generic_description.clear();
+    assert_eq!(badge.description_for(false), "Write every day");
+}
+
+#[cfg(feature = "url")]
+#[test]
+fn test_project_links() {
+    let project = |url: &str| ProjectData {
+        playlist_url: Some(url.into()),
+        pinterest_url: None,
+        ..Default::default()
+    };
+
+    assert_eq!(
+        project(" https://example.com/playlist ")
+            .playlist_link()
+            .map(String::from),
+        Some("https://example.com/playlist".into())
+    );
+    assert_eq!(project("").playlist_link(), None);
+    assert_eq!(project("my playlist").playlist_link(), None);
+    assert_eq!(project("javascript:alert(1)").playlist_link(), None);
+    assert_eq!(project("").pinterest_link(), None);
+}
+
+#[test]
+fn test_image_url_formats() {
+    let group = |avatar: serde_json::Value| {
+        serde_json::from_str::<GroupData>(
+            &json!({
+                "approved-by-id": 0,
+                "avatar": avatar,
+                "cancelled-by-id": 0,
+                "created-at": "2023-11-01T00:00:00Z",
+                "group-type": "region",
+                "name": "Somewhere",
+                "plate": { "src": "https://example.com/plate.png" },
+                "slug": "somewhere",
+                "updated-at": "2023-11-01T00:00:00Z",
+            })
+            .to_string(),
+        )
+        .expect("Couldn't decode a group")
+    };
+
+    let plain = group(json!("https://example.com/avatar.png"));
+    assert_eq!(
+        plain.avatar.as_deref(),
+        Some("https://example.com/avatar.png")
+    );
+    assert_eq!(
+        plain.plate.as_deref(),
+        Some("https://example.com/plate.png")
+    );
+
+    let object = group(json!({ "src": "https://example.com/avatar.png" }));
+    assert_eq!(
+        object.avatar.as_deref(),
+        Some("https://example.com/avatar.png")
+    );
+
+    assert_eq!(group(json!(null)).avatar, None);
+}
+
+#[cfg(feature = "url")]
+#[test]
+fn test_group_links() {
+    let link: GroupExternalLinkData = serde_json::from_value(json!({
+        "group-id": 1,
+        "label": "Discord",
+        "url": "https://discord.gg/example",
+    }))
+    .unwrap();
+    assert_eq!(
+        link.link().map(String::from),
+        Some("https://discord.gg/example".into())
+    );
+
+    let invalid: GroupExternalLinkData = serde_json::from_value(json!({
+        "group-id": 1,
+        "label": null,
+        "url": "our forum",
+    }))
+    .unwrap();
+    assert_eq!(invalid.link(), None);
+}
+
+#[test]
+fn test_page_cursor_roundtrip() {
+    let cursor = PageCursor::new(NanoKind::ProjectSession, &[("project_id", 12)]);
+    assert_eq!(cursor.page, Some(1));
+    assert!(!cursor.is_done());
+
+    let raw = serde_json::to_value(&cursor).unwrap();
+    assert_eq!(raw["kind"], "project-sessions");
+
+    let back: PageCursor = serde_json::from_value(raw).unwrap();
+    assert_eq!(back, cursor);
+}
+
+#[test]
+fn test_collection_record_count() {
+    let without: CollectionResponse<GenreObject> =
+        serde_json::from_str(&format!(r#"{{"data": [{GENRE}], "included": null}}"#)).unwrap();
+    assert_eq!(without.record_count(), None);
+
+    let hidden: CollectionResponse<GenreObject> =
+        serde_json::from_str(r#"{"data": [], "included": null, "meta": {"record-count": 3}}"#)
+            .unwrap();
+    assert!(hidden.data.is_empty());
+    assert_eq!(hidden.record_count(), Some(3));
+}
+
+#[test]
+fn test_relation_info_serialization() {
+    let rel = RelationInfo {
+        included: [
+            (
+                NanoKind::Project,
+                vec![ObjectRef {
+                    id: 1,
+                    kind: NanoKind::Project,
+                    extra: Default::default(),
+                }],
+            ),
+            (
+                NanoKind::Genre,
+                vec![
+                    ObjectRef {
+                        id: 2,
+                        kind: NanoKind::Genre,
+                        extra: Default::default(),
+                    },
+                    ObjectRef {
+                        id: 3,
+                        kind: NanoKind::Genre,
+                        extra: Default::default(),
+                    },
+                ],
+            ),
+        ]
+        .into_iter()
+        .collect(),
+        relations: HashMap::new(),
+    };
+
+    assert_eq!(
+        serde_json::to_value(&rel).unwrap(),
+        json!({
+            "project": {
+                "data": { "id": "1", "type": "projects" }
+            },
+            "genres": {
+                "data": [
+                    { "id": "2", "type": "genres" },
+                    { "id": "3", "type": "genres" },
+                ]
+            },
+        })
+    );
+}
+
+#[test]
+fn test_relation_info_roundtrip() {
+    let payloads = [
+        json!({}),
+        json!({
+            "user": {
+                "links": {
+                    "self": "/projects/1/relationships/user",
+                    "related": "/projects/1/user"
+                }
+            },
+            "project-challenges": {
+                "links": {
+                    "self": "/projects/1/relationships/project-challenges",
+                    "related": "/projects/1/project-challenges"
+                }
+            },
+        }),
+        json!({
+            "user": {
+                "links": {
+                    "self": "/projects/1/relationships/user",
+                    "related": "/projects/1/user"
+                },
+                "data": { "id": "2", "type": "users" }
+            },
+            "project-challenges": {
+                "links": {
+                    "self": "/projects/1/relationships/project-challenges",
+                    "related": "/projects/1/project-challenges"
+                },
+                "data": [{ "id": "3", "type": "project-challenges" }]
+            },
+            "genres": {
+                "links": {
+                    "self": "/projects/1/relationships/genres",
+                    "related": "/projects/1/genres"
+                },
+                "data": []
+            },
+        }),
+        json!({
+            "project": {
+                "data": { "id": "12", "type": "projects" }
+            },
+            "project-challenge": {
+                "data": { "id": "34", "type": "project-challenges" }
+            },
+        }),
+    ];
+
+    for payload in payloads {
+        let rel: RelationInfo = serde_json::from_value(payload.clone())
+            .unwrap_or_else(|err| panic!("Couldn't decode {payload}: {err}"));
+        assert_eq!(serde_json::to_value(&rel).unwrap(), payload);
+    }
+
+    let rel: RelationInfo = serde_json::from_value(json!({
+        "project-challenges": {
+            "links": {
+                "self": "/projects/1/relationships/project-challenges",
+                "related": "/projects/1/project-challenges"
+            },
+            "data": { "id": "3", "type": "project-challenges" }
+        },
+    }))
+    .unwrap();
+    assert_eq!(rel.included[&NanoKind::ProjectChallenge].len(), 1);
+    assert!(rel.relations.contains_key(&NanoKind::ProjectChallenge));
+
+    // Null references are normalized away
+    let user = json!({
+        "links": {
+            "self": "/projects/1/relationships/user",
+            "related": "/projects/1/user"
+        }
+    });
+    let mut payload = user.clone();
+    payload["data"] = serde_json::Value::Null;
+    let rel: RelationInfo = serde_json::from_value(json!({ "user": payload })).unwrap();
+    assert!(rel.included.is_empty());
+    assert_eq!(serde_json::to_value(&rel).unwrap(), json!({ "user": user }));
+}
+
+const KINDS: &[NanoKind] = &[
+    NanoKind::Badge,
+    NanoKind::Challenge,
+    NanoKind::ChildPost,
+    NanoKind::DailyAggregate,
+    NanoKind::ExternalLink,
+    NanoKind::FavoriteAuthor,
+    NanoKind::FavoriteBook,
+    NanoKind::Genre,
+    NanoKind::Group,
+    NanoKind::GroupExternalLink,
+    NanoKind::Location,
+    NanoKind::NanoMessage,
+    NanoKind::Notification,
+    NanoKind::Page,
+    NanoKind::Post,
+    NanoKind::Project,
+    NanoKind::ProjectSession,
+    NanoKind::StopWatch,
+    NanoKind::Timer,
+    NanoKind::User,
+    NanoKind::WritingLocation,
+    NanoKind::WritingMethod,
+    NanoKind::ChildPostPost,
+    NanoKind::GroupUser,
+    NanoKind::LocationGroup,
+    NanoKind::PostPage,
+    NanoKind::ProjectChallenge,
+    NanoKind::UserBadge,
+];
+
+fn any_kind() -> impl Strategy<Value = NanoKind> {
+    prop::sample::select(KINDS)
+}
+
+/// A link to a relation of the given kind, named in the singular, the plural, or neither
+fn any_relation_link(kind: NanoKind) -> impl Strategy<Value = RelationLink> {
+    prop_oneof![
+        Just(kind.api_unique_name().to_string()),
+        Just(kind.api_name().to_string()),
+        "[a-z]{1,8}",
+    ]
+    .prop_map(|name| RelationLink {
+        this: format!("/objects/1/relationships/{name}"),
+        related: format!("/objects/1/{name}"),
+    })
+}
+
+fn any_object_refs() -> impl Strategy<Value = Vec<ObjectRef>> {
+    prop::collection::vec(
+        (any::<u64>(), any_kind()).prop_map(|(id, kind)| ObjectRef {
+            id,
+            kind,
+            extra: Default::default(),
+        }),
+        0..4,
+    )
+}
+
+/// Relations of any kinds, each with a link, references to zero, one, or many objects, or both
+fn any_relation_info() -> impl Strategy<Value = RelationInfo> {
+    prop::collection::vec(
+        any_kind().prop_flat_map(|kind| {
+            (
+                Just(kind),
+                prop::option::of(any_relation_link(kind)),
+                prop::option::of(any_object_refs()),
+            )
+        }),
+        0..6,
+    )
+    .prop_map(|entries| {
+        let mut rel = RelationInfo {
+            included: HashMap::new(),
+            relations: HashMap::new(),
+        };
+
+        for (kind, links, refs) in entries {
+            if let Some(links) = links {
+                rel.relations.insert(kind, links);
+            }
+            if let Some(refs) = refs {
+                rel.included.insert(kind, refs);
+            }
+        }
+
+        rel
+    })
+}
+
+proptest! {
+    #[test]
+    fn test_relation_info_roundtrip_any(rel in any_relation_info()) {
+        let json = serde_json::to_string(&rel).unwrap();
+        let back: RelationInfo = serde_json::from_str(&json)
+            .unwrap_or_else(|err| panic!("Couldn't decode {json}: {err}"));
+
+        let links = |rel: &RelationInfo| {
+            rel.relations
+                .iter()
+                .map(|(kind, link)| (*kind, link.this.clone(), link.related.clone()))
+                .collect::<HashSet<_>>()
+        };
+
+        prop_assert_eq!(&back.included, &rel.included);
+        prop_assert_eq!(links(&back), links(&rel));
+    }
+}
+
+#[test]
+fn test_has_won() {
+    let challenge = |current_count, won_at| ProjectChallengeData {
+        current_count,
+        goal: 50000,
+        won_at,
+        ..Default::default()
+    };
+
+    assert!(!challenge(49999, None).has_won());
+    assert!(challenge(50000, None).has_won());
+    assert!(challenge(12, Some(Utc::now())).has_won());
+}
+
+#[test]
+fn test_next_milestone() {
+    let challenge = |current_count| ProjectChallengeData {
+        current_count,
+        goal: 50000,
+        starts_at: NaiveDate::from_ymd_opt(2023, 11, 1).unwrap(),
+        ends_at: NaiveDate::from_ymd_opt(2023, 11, 30).unwrap(),
+        ..Default::default()
+    };
+    let day = |d| NaiveDate::from_ymd_opt(2023, 11, d).unwrap();
+
+    assert_eq!(challenge(0).next_milestone(), Some((day(8), 12500)));
+    assert_eq!(challenge(12500).next_milestone(), Some((day(15), 25000)));
+    assert_eq!(challenge(30000).next_milestone(), Some((day(23), 37500)));
+    assert_eq!(challenge(49999).next_milestone(), Some((day(30), 50000)));
+    assert_eq!(challenge(50000).next_milestone(), None);
+}
+
+#[test]
+fn test_null_default() {
+    #[derive(Deserialize)]
+    struct Unread {
+        #[serde(default, deserialize_with = "de_null_default")]
+        count: u64,
+    }
+
+    for (raw, expected) in [(r#"{"count": 3}"#, 3), (r#"{"count": null}"#, 0), ("{}", 0)] {
+        let Unread { count } = serde_json::from_str(raw).unwrap();
+        assert_eq!(count, expected, "{raw} didn't decode as {expected}");
+    }
+}
+
+#[test]
+fn test_settings_diff() {
+    let before: UserSettings = serde_json::from_value(json!({
+        "email-blog-posts": true,
+        "email-buddy-requests": true,
+        "email-events-in-home-region": true,
+        "email-nanomessages-buddies": true,
+        "email-nanomessages-hq": true,
+        "email-nanomessages-mls": true,
+        "email-nanowrimo-updates": true,
+        "email-newsletter": false,
+        "email-writing-reminders": false,
+        "notification-buddy-activities": true,
+        "notification-buddy-requests": true,
+        "notification-events-in-home-region": true,
+        "notification-goal-milestones": true,
+        "notification-nanomessages-buddies": true,
+        "notification-nanomessages-hq": true,
+        "notification-nanomessages-mls": true,
+        "notification-new-badges": true,
+        "notification-sprint-invitation": true,
+        "notification-sprint-start": true,
+        "notification-writing-reminders": true,
+        "privacy-send-nanomessages": 1,
+        "privacy-view-buddies": 2,
+        "privacy-view-profile": 2,
+        "privacy-view-projects": 1,
+        "privacy-view-search": 2,
+        "privacy-visibility-activity-logs": true,
+        "privacy-visibility-buddy-lists": true,
+        "privacy-visibility-regions": true,
+    }))
+    .unwrap();
+
+    let mut after = before.clone();
+    assert!(before.diff(&after).is_empty());
+
+    after.email.newsletter = true;
+    after.privacy.view_projects = PrivacySetting::Private;
+
+    let diff = before.diff(&after);
+    assert_eq!(diff.len(), 2);
+    assert_eq!(diff["email-newsletter"], json!(true));
+    assert_eq!(diff["privacy-view-projects"], json!(0));
+}
+
+#[test]
+fn test_write_and_update_views() {
+    let body = json!({
+        "data": {
+            "type": "projects",
+            "attributes": {
+                "created-at": "2023-11-01T00:00:00Z",
+                "summary": null,
+                "title": "Untitled",
+            },
+        }
+    });
+
+    assert_eq!(
+        write_view(&body).unwrap()["data"]["attributes"],
+        json!({ "title": "Untitled" })
+    );
+    assert_eq!(
+        update_view(&body).unwrap()["data"]["attributes"],
+        json!({ "summary": null, "title": "Untitled" })
+    );
+}
+
+#[test]
+fn test_relation_link_cardinality() {
+    let link = |related: &str| RelationLink {
+        this: String::new(),
+        related: related.to_string(),
+    };
+
+    assert_eq!(link("/projects/12/user").is_singleton(), Some(true));
+    assert_eq!(
+        link("/projects/12/project-challenges").is_singleton(),
+        Some(false)
+    );
+    assert_eq!(
+        link("/groups/3/group-users?page=2").is_singleton(),
+        Some(false)
+    );
+    assert_eq!(link("/users/5/status").is_singleton(), None);
+}
+
+#[test]
+fn test_new_user_without_halo_or_laurels() {
+    let raw = json!({
+        "admin-level": 0,
+        "avatar": null,
+        "bio": null,
+        "confirmed-at": "2023-10-30T12:00:00Z",
+        "created-at": "2023-10-30T12:00:00Z",
+        "discourse-username": null,
+        "email": null,
+        "location": null,
+        "name": "newwriter",
+        "notifications-viewed-at": "2023-10-30T12:00:00Z",
+        "plate": null,
+        "postal-code": null,
+        "registration-path": "email",
+        "setting-session-count-by-session": 0,
+        "setting-session-more-info": false,
+        "slug": "newwriter",
+        "stats-projects": 0,
+        "stats-projects-enabled": true,
+        "stats-streak": 0,
+        "stats-streak-enabled": true,
+        "stats-word-count": 0,
+        "stats-word-count-enabled": true,
+        "stats-wordiest": 0,
+        "stats-wordiest-enabled": true,
+        "stats-writing-pace": null,
+        "stats-writing-pace-enabled": true,
+        "stats-years-done": null,
+        "stats-years-enabled": true,
+        "stats-years-won": null,
+        "time-zone": "Pacific/Auckland",
+    })
+    .to_string();
+
+    let user: UserData =
+        serde_json::from_str(&raw).expect("Couldn't decode a new user without halo or laurels");
+
+    assert!(!user.halo);
+    assert_eq!(user.laurels, 0);
+}
+
+#[test]
+fn test_attributes_as() {
+    #[derive(Deserialize)]
+    struct Named {
+        name: String,
+    }
+
+    let genre: Object = serde_json::from_str(GENRE).unwrap();
+
+    assert_eq!(genre.attributes_json().unwrap()["user-id"], json!(1));
+    assert_eq!(genre.attributes_as::<Named>().unwrap().name, "Fantasy");
+}
+
+#[test]
+fn test_display_name() {
+    let genre: Object = serde_json::from_str(GENRE).unwrap();
+    assert_eq!(genre.display_name(), Some("Fantasy"));
+}
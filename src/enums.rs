@@ -2,9 +2,13 @@ use std::convert::TryFrom;
 
 use serde::{Deserialize, Serialize};
 
-#[derive(Deserialize, Serialize, PartialEq, Eq, Debug, Copy, Clone)]
+use crate::NanoKind;
+
+#[derive(Deserialize, Serialize, PartialEq, Eq, PartialOrd, Ord, Debug, Copy, Clone, Default)]
 #[serde(try_from = "u8", into = "u8")]
+#[non_exhaustive]
 pub enum PrivacySetting {
+    #[default]
     Private,
     Buddies,
     Anyone,
@@ -33,9 +37,11 @@ impl From<PrivacySetting> for u8 {
     }
 }
 
-#[derive(Deserialize, Serialize, PartialEq, Eq, Debug, Copy, Clone)]
+#[derive(Deserialize, Serialize, PartialEq, Eq, Debug, Copy, Clone, Default)]
 #[serde(try_from = "&str", into = "&'static str")]
+#[non_exhaustive]
 pub enum ProjectStatus {
+    #[default]
     Prepping,
     InProgress,
     Drafted,
@@ -70,11 +76,13 @@ impl From<ProjectStatus> for &'static str {
     }
 }
 
-#[derive(Deserialize, Serialize, PartialEq, Eq, Debug, Copy, Clone)]
+#[derive(Deserialize, Serialize, PartialEq, Eq, Debug, Copy, Clone, Default)]
 #[serde(try_from = "u8", into = "u8")]
+#[non_exhaustive]
 pub enum EventType {
     NanoWrimo,
     CampNano,
+    #[default]
     Custom,
 }
 
@@ -101,12 +109,14 @@ impl From<EventType> for u8 {
     }
 }
 
-#[derive(Deserialize, Serialize, PartialEq, Eq, Debug, Copy, Clone)]
+#[derive(Deserialize, Serialize, PartialEq, Eq, Debug, Copy, Clone, Default)]
 #[serde(try_from = "&str", into = "&'static str")]
+#[non_exhaustive]
 pub enum GroupType {
     Everyone,
     Region,
     Buddies,
+    #[default]
     WritingGroup,
     Event,
 }
@@ -138,9 +148,11 @@ impl From<GroupType> for &'static str {
     }
 }
 
-#[derive(Deserialize, Serialize, PartialEq, Eq, Debug, Copy, Clone)]
+#[derive(Deserialize, Serialize, PartialEq, Eq, Debug, Copy, Clone, Default)]
 #[serde(try_from = "&str", into = "&'static str")]
+#[non_exhaustive]
 pub enum EntryMethod {
+    #[default]
     Join,
     Creator,
     Create,
@@ -177,6 +189,7 @@ impl From<EntryMethod> for &'static str {
 
 #[derive(Deserialize, Serialize, PartialEq, Eq, Debug, Copy, Clone)]
 #[serde(try_from = "u8", into = "u8")]
+#[non_exhaustive]
 pub enum AdminLevel {
     User,
     Admin,
@@ -205,6 +218,7 @@ impl From<AdminLevel> for u8 {
 
 #[derive(Deserialize, Serialize, PartialEq, Eq, Debug, Copy, Clone)]
 #[serde(try_from = "&str", into = "&'static str")]
+#[non_exhaustive]
 pub enum ActionType {
     BadgeAwarded,
     BuddiesPage,
@@ -237,9 +251,22 @@ impl From<ActionType> for &'static str {
     }
 }
 
+impl ActionType {
+    /// The kind of object the `action-id` of a notification with this action refers to
+    pub fn target_kind(&self) -> NanoKind {
+        match self {
+            ActionType::BadgeAwarded => NanoKind::Badge,
+            ActionType::BuddiesPage => NanoKind::User,
+            ActionType::NanoMessages => NanoKind::NanoMessage,
+            ActionType::ProjectsPage => NanoKind::Project,
+        }
+    }
+}
+
 /// Whether to display the notification in the 'recent notifications'
 #[derive(Deserialize, Serialize, PartialEq, Eq, Debug, Copy, Clone)]
 #[serde(try_from = "u8", into = "u8")]
+#[non_exhaustive]
 pub enum DisplayStatus {
     AllNotifs,
     RecentNotifs,
@@ -267,9 +294,11 @@ impl From<DisplayStatus> for u8 {
 }
 
 // TODO: This may be wrong
-#[derive(Deserialize, Serialize, PartialEq, Eq, Debug, Copy, Clone)]
+#[derive(Deserialize, Serialize, PartialEq, Eq, Debug, Copy, Clone, Default)]
 #[serde(try_from = "u8", into = "u8")]
+#[non_exhaustive]
 pub enum WritingType {
+    #[default]
     Novel,
     ShortStories,
     Memoir,
@@ -312,6 +341,7 @@ impl From<WritingType> for u8 {
 
 #[derive(Deserialize, Serialize, PartialEq, Eq, Debug, Copy, Clone)]
 #[serde(try_from = "&str", into = "&'static str")]
+#[non_exhaustive]
 pub enum ContentType {
     GeneralContent,
     StackedContent,
@@ -358,6 +388,7 @@ impl From<ContentType> for &'static str {
 
 #[derive(Deserialize, Serialize, PartialEq, Eq, Debug, Copy, Clone)]
 #[serde(try_from = "&str", into = "&'static str")]
+#[non_exhaustive]
 pub enum RegistrationPath {
     Email,
     Facebook,
@@ -389,6 +420,7 @@ impl From<RegistrationPath> for &'static str {
 
 #[derive(Deserialize, Serialize, PartialEq, Eq, Debug, Copy, Clone)]
 #[serde(try_from = "&str", into = "&'static str")]
+#[non_exhaustive]
 pub enum BadgeType {
     WordCount,
     SelfAwarded,
@@ -420,6 +452,7 @@ impl From<BadgeType> for &'static str {
 
 #[derive(Deserialize, Serialize, PartialEq, Eq, Debug, Copy, Clone)]
 #[serde(try_from = "u8", into = "u8")]
+#[non_exhaustive]
 pub enum JoiningRule {
     AdminOnly,
     AnyUser,
@@ -448,6 +481,7 @@ impl From<JoiningRule> for u8 {
 
 #[derive(Deserialize, Serialize, PartialEq, Eq, Debug, Copy, Clone, Default)]
 #[serde(try_from = "u8", into = "u8")]
+#[non_exhaustive]
 pub enum UnitType {
     #[default]
     Words,
@@ -478,6 +512,7 @@ impl From<UnitType> for u8 {
 // This may someday be replaced with NanoKind
 #[derive(Deserialize, Serialize, PartialEq, Eq, Debug, Copy, Clone)]
 #[serde(try_from = "&str", into = "&'static str")]
+#[non_exhaustive]
 pub enum AdheresTo {
     Unknown,
     User,
@@ -507,8 +542,9 @@ impl From<AdheresTo> for &'static str {
     }
 }
 
-#[derive(Deserialize, Serialize, PartialEq, Eq, Debug, Copy, Clone)]
+#[derive(Deserialize, Serialize, PartialEq, Eq, PartialOrd, Ord, Debug, Copy, Clone)]
 #[serde(try_from = "u8", into = "u8")]
+#[non_exhaustive]
 pub enum Feeling {
     Upset,
     Stressed,
@@ -546,6 +582,7 @@ impl From<Feeling> for u8 {
 
 #[derive(Deserialize, Serialize, PartialEq, Eq, Debug, Copy, Clone)]
 #[serde(from = "u8", into = "u8")]
+#[non_exhaustive]
 pub enum Where {
     Home,
     Office,
@@ -566,9 +603,9 @@ impl From<u8> for Where {
     }
 }
 
-impl Into<u8> for Where {
-    fn into(self) -> u8 {
-        match self {
+impl From<Where> for u8 {
+    fn from(val: Where) -> Self {
+        match val {
             Where::Home => 0,
             Where::Office => 1,
             Where::Library => 2,
@@ -580,6 +617,7 @@ impl Into<u8> for Where {
 
 #[derive(Deserialize, Serialize, PartialEq, Eq, Debug, Copy, Clone)]
 #[serde(from = "u64", into = "u64")]
+#[non_exhaustive]
 pub enum How {
     ByHand,
     Typewriter,
@@ -600,9 +638,9 @@ impl From<u64> for How {
     }
 }
 
-impl Into<u64> for How {
-    fn into(self) -> u64 {
-        match self {
+impl From<How> for u64 {
+    fn from(val: How) -> Self {
+        match val {
             How::ByHand => 0,
             How::Typewriter => 1,
             How::Laptop => 2,
@@ -612,10 +650,51 @@ impl Into<u64> for How {
     }
 }
 
+/// The time of day a user reports doing most of their writing for a project challenge
+///
+/// Unconfirmed: the meaning of each value is a guess from the name of the field, and hasn't been
+/// checked against a real response. Values outside of these are kept as-is in [`When::Other`].
 #[derive(Deserialize, Serialize, PartialEq, Eq, Debug, Copy, Clone)]
+#[serde(from = "u64", into = "u64")]
+#[non_exhaustive]
+pub enum When {
+    Morning,
+    Afternoon,
+    Evening,
+    Night,
+    Other(u64),
+}
+
+impl From<u64> for When {
+    fn from(val: u64) -> When {
+        match val {
+            0 => When::Morning,
+            1 => When::Afternoon,
+            2 => When::Evening,
+            3 => When::Night,
+            _ => When::Other(val),
+        }
+    }
+}
+
+impl From<When> for u64 {
+    fn from(val: When) -> Self {
+        match val {
+            When::Morning => 0,
+            When::Afternoon => 1,
+            When::Evening => 2,
+            When::Night => 3,
+            When::Other(val) => val,
+        }
+    }
+}
+
+#[derive(Deserialize, Serialize, PartialEq, Eq, Debug, Copy, Clone, Default)]
 #[serde(try_from = "i8", into = "i8")]
+#[non_exhaustive]
 pub enum InvitationStatus {
     Blocked,
+    #[default]
     Sent,
     Accepted,
 }
@@ -1,7 +1,25 @@
 use std::{error, fmt};
 
-use crate::ErrorData;
+use crate::{ErrorData, NanoKind, PrivacySetting};
 use reqwest::StatusCode;
+use serde_path_to_error::Segment;
+
+#[cfg(test)]
+mod tests;
+
+/// A single step along the path to a value in a response which couldn't be decoded
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum PathSegment {
+    /// An index into an array
+    Index(usize),
+    /// A key of an object
+    Key(String),
+    /// A variant of an enum
+    Variant(String),
+    /// A step that couldn't be determined
+    Unknown,
+}
 
 /// A common error type returned from Nano API operations
 #[derive(Debug)]
@@ -13,7 +31,12 @@ pub enum Error {
     BadJSON(serde_json::Error),
     /// Valid JSON but can't understand it
     ResponseDecoding {
+        /// The path to the value which failed to decode, like `data.attributes.primary`
         path: String,
+        /// The same path, split into its individual steps
+        segments: Vec<PathSegment>,
+        /// The kind of the innermost object along the path, if it could be determined
+        kind: Option<NanoKind>,
         err: serde_json::Error,
     },
     /// An error induced by a failed reqwest
@@ -22,6 +45,28 @@ pub enum Error {
     SimpleNanoError(StatusCode, String),
     /// An error from Nano with multiple complex inner values
     NanoErrors(Vec<ErrorData>),
+    /// Some operations of a batch failed, listed by their index in the batch. The others
+    /// succeeded, and are not rolled back.
+    Batch(Vec<(usize, Error)>),
+    /// The body of a response wasn't valid UTF-8, so couldn't even be read as JSON
+    BadEncoding {
+        /// The first bytes of the body, as received
+        snippet: Vec<u8>,
+        err: std::str::Utf8Error,
+    },
+    /// The body of a response was larger than allowed (see
+    /// [`NanoClient::with_max_response_size`](crate::NanoClient::with_max_response_size))
+    ResponseTooLarge {
+        /// The maximum size allowed, in bytes
+        limit: usize,
+    },
+    /// A string which isn't a valid slug (see [`Slug`](crate::Slug))
+    InvalidSlug(String),
+    /// The data belongs to a user whose privacy settings hide it from the current user
+    PrivacyRestricted {
+        /// Who the user allows to see the data
+        setting: PrivacySetting,
+    },
 }
 
 impl fmt::Display for Error {
@@ -29,7 +74,7 @@ impl fmt::Display for Error {
         match self {
             Error::NoCredentials => write!(f, "No credentials available"),
             Error::BadJSON(err) => write!(f, "JSON Error: {err}"),
-            Error::ResponseDecoding { path, err } => {
+            Error::ResponseDecoding { path, err, .. } => {
                 write!(f, "Error decoding response, at {path}: {err}")
             }
             Error::ReqwestError(err) => write!(f, "Reqwest Error: {err}"),
@@ -45,14 +90,119 @@ impl fmt::Display for Error {
                     err.title, err.code, err.detail, err.status
                 )
             }),
+            Error::Batch(errs) => {
+                write!(f, "{} operation(s) of the batch failed", errs.len())?;
+                errs.iter()
+                    .try_for_each(|(index, err)| write!(f, "; #{index}: {err}"))
+            }
+            Error::BadEncoding { snippet, err } => write!(
+                f,
+                "Response isn't valid UTF-8 ({err}), starting with: {}",
+                String::from_utf8_lossy(snippet).escape_debug()
+            ),
+            Error::ResponseTooLarge { limit } => {
+                write!(f, "Response is larger than the limit of {limit} bytes")
+            }
+            Error::InvalidSlug(slug) => write!(f, "Invalid slug: {slug:?}"),
+            Error::PrivacyRestricted { setting } => write!(
+                f,
+                "Hidden by the user's privacy settings (visible to: {setting:?})"
+            ),
+        }
+    }
+}
+
+impl Error {
+    /// Build a [`Error::ResponseDecoding`] from the path at which decoding failed, using the raw
+    /// response to figure out the kind of object the failure happened in
+    pub(crate) fn response_decoding(
+        path: &serde_path_to_error::Path,
+        raw: &serde_json::Value,
+        err: serde_json::Error,
+    ) -> Error {
+        let segments = path
+            .iter()
+            .map(|segment| match segment {
+                Segment::Seq { index } => PathSegment::Index(*index),
+                Segment::Map { key } => PathSegment::Key(key.clone()),
+                Segment::Enum { variant } => PathSegment::Variant(variant.clone()),
+                Segment::Unknown => PathSegment::Unknown,
+            })
+            .collect::<Vec<_>>();
+
+        let mut kind = None;
+        let mut current = Some(raw);
+        for segment in &segments {
+            let Some(val) = current else { break };
+
+            if let Some(name) = val.get("type").and_then(|name| name.as_str()) {
+                kind = NanoKind::from_name(name).ok().or(kind);
+            }
+
+            current = match segment {
+                PathSegment::Index(index) => val.get(index),
+                PathSegment::Key(key) => val.get(key),
+                PathSegment::Variant(_) => Some(val),
+                PathSegment::Unknown => None,
+            };
+        }
+
+        Error::ResponseDecoding {
+            path: path.to_string(),
+            segments,
+            kind,
+            err,
+        }
+    }
+
+    /// Build a [`Error::BadEncoding`] from a response body which isn't valid UTF-8, keeping only
+    /// its start
+    pub(crate) fn bad_encoding(body: &[u8], err: std::str::Utf8Error) -> Error {
+        const SNIPPET_LEN: usize = 64;
+
+        Error::BadEncoding {
+            snippet: body[..body.len().min(SNIPPET_LEN)].to_vec(),
+            err,
+        }
+    }
+
+    /// For a [`Error::ResponseDecoding`], the name of the field which failed to decode, if the
+    /// failure was in a field
+    pub fn decoding_field(&self) -> Option<&str> {
+        match self {
+            Error::ResponseDecoding { segments, .. } => match segments.last() {
+                Some(PathSegment::Key(key)) => Some(key),
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+
+    /// The HTTP status code of the API response this error comes from, if it comes from one.
+    /// This tells apart, for example, data hidden from the current user (403) from data which
+    /// doesn't exist (404).
+    pub fn status_code(&self) -> Option<StatusCode> {
+        match self {
+            Error::SimpleNanoError(code, _) => Some(*code),
+            Error::NanoErrors(errs) => errs
+                .iter()
+                .find_map(|err| StatusCode::from_u16(u16::try_from(err.status).ok()?).ok()),
+            Error::ReqwestError(err) => err.status(),
+            _ => None,
         }
     }
+
+    /// Whether this is the API refusing access to the requested data
+    pub(crate) fn is_forbidden(&self) -> bool {
+        self.status_code() == Some(StatusCode::FORBIDDEN)
+    }
 }
 
 impl error::Error for Error {
     fn source(&self) -> Option<&(dyn error::Error + 'static)> {
         match self {
             Error::ReqwestError(err) => Some(err),
+            Error::BadEncoding { err, .. } => Some(err),
             _ => None,
         }
     }
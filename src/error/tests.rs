@@ -0,0 +1,75 @@
+use super::*;
+use crate::{CollectionResponse, ProjectObject};
+
+fn decode_error(raw: &str) -> Error {
+    let val: serde_json::Value = serde_json::from_str(raw).unwrap();
+    let jd = &mut serde_json::Deserializer::from_str(raw);
+
+    let err = serde_path_to_error::deserialize::<_, CollectionResponse<ProjectObject>>(jd)
+        .expect_err("Response was expected to fail decoding");
+
+    let path = err.path().clone();
+    Error::response_decoding(&path, &val, err.into_inner())
+}
+
+#[test]
+fn test_response_decoding_path() {
+    let err = decode_error(
+        r#"{"data": [{"id": "1", "type": "projects", "attributes": {"primary": "yes"}}]}"#,
+    );
+
+    let Error::ResponseDecoding {
+        path,
+        segments,
+        kind,
+        ..
+    } = &err
+    else {
+        panic!("Expected a ResponseDecoding error, got {err:?}");
+    };
+
+    assert_eq!(path, "data[0].attributes.primary");
+    assert_eq!(
+        segments,
+        &[
+            PathSegment::Key("data".into()),
+            PathSegment::Index(0),
+            PathSegment::Key("attributes".into()),
+            PathSegment::Key("primary".into()),
+        ]
+    );
+    assert_eq!(kind, &Some(NanoKind::Project));
+    assert_eq!(err.decoding_field(), Some("primary"));
+}
+
+#[test]
+fn test_is_forbidden() {
+    assert!(Error::SimpleNanoError(StatusCode::FORBIDDEN, "Forbidden".into()).is_forbidden());
+    assert!(!Error::SimpleNanoError(StatusCode::NOT_FOUND, "Page Not Found".into()).is_forbidden());
+
+    let errors: Vec<ErrorData> = serde_json::from_str(
+        r#"[{"code": "403", "detail": "Not allowed", "status": "403", "title": "Forbidden"}]"#,
+    )
+    .unwrap();
+    let err = Error::NanoErrors(errors);
+    assert_eq!(err.status_code(), Some(StatusCode::FORBIDDEN));
+    assert!(err.is_forbidden());
+
+    assert_eq!(Error::NoCredentials.status_code(), None);
+    assert!(!Error::NoCredentials.is_forbidden());
+}
+
+#[test]
+fn test_bad_encoding_snippet() {
+    let mut body = b"<html>".to_vec();
+    body.extend([0xff; 100]);
+
+    let err = Error::bad_encoding(&body, std::str::from_utf8(&body).unwrap_err());
+
+    let Error::BadEncoding { snippet, .. } = &err else {
+        panic!("Expected a BadEncoding error, got {err:?}");
+    };
+    assert_eq!(snippet.len(), 64);
+    assert!(snippet.starts_with(b"<html>"));
+    assert!(err.to_string().contains("<html>"));
+}
@@ -38,38 +38,39 @@ pub enum NanoKind {
 }
 
 impl NanoKind {
-    /// Convert the name of a type from the Nano API into a NanoKind
+    /// Convert the name of a type from the Nano API into a NanoKind. Both the plural and the
+    /// singular names are accepted, as relations to a single object use the latter.
     pub fn from_name(name: &str) -> Result<NanoKind, String> {
         Ok(match name {
             "badges" | "badge" => NanoKind::Badge,
             "challenges" | "challenge" => NanoKind::Challenge,
-            "child-posts" => NanoKind::ChildPost,
-            "daily-aggregates" => NanoKind::DailyAggregate,
-            "external-links" => NanoKind::ExternalLink,
-            "favorite-authors" => NanoKind::FavoriteAuthor,
-            "favorite-books" => NanoKind::FavoriteBook,
-            "genres" => NanoKind::Genre,
+            "child-posts" | "child-post" => NanoKind::ChildPost,
+            "daily-aggregates" | "daily-aggregate" => NanoKind::DailyAggregate,
+            "external-links" | "external-link" => NanoKind::ExternalLink,
+            "favorite-authors" | "favorite-author" => NanoKind::FavoriteAuthor,
+            "favorite-books" | "favorite-book" => NanoKind::FavoriteBook,
+            "genres" | "genre" => NanoKind::Genre,
             "groups" | "group" => NanoKind::Group,
-            "group-external-links" => NanoKind::GroupExternalLink,
+            "group-external-links" | "group-external-link" => NanoKind::GroupExternalLink,
             "locations" | "location" => NanoKind::Location,
-            "nanomessages" => NanoKind::NanoMessage,
-            "notifications" => NanoKind::Notification,
-            "pages" => NanoKind::Page,
-            "posts" => NanoKind::Post,
+            "nanomessages" | "nanomessage" => NanoKind::NanoMessage,
+            "notifications" | "notification" => NanoKind::Notification,
+            "pages" | "page" => NanoKind::Page,
+            "posts" | "post" => NanoKind::Post,
             "projects" | "project" => NanoKind::Project,
-            "project-sessions" => NanoKind::ProjectSession,
-            "stopwatches" => NanoKind::StopWatch,
-            "timers" => NanoKind::Timer,
+            "project-sessions" | "project-session" => NanoKind::ProjectSession,
+            "stopwatches" | "stopwatch" => NanoKind::StopWatch,
+            "timers" | "timer" => NanoKind::Timer,
             "users" | "user" => NanoKind::User,
-            "writing-locations" => NanoKind::WritingLocation,
-            "writing-methods" => NanoKind::WritingMethod,
+            "writing-locations" | "writing-location" => NanoKind::WritingLocation,
+            "writing-methods" | "writing-method" => NanoKind::WritingMethod,
 
-            "child-post-posts" => NanoKind::ChildPostPost,
-            "group-users" => NanoKind::GroupUser,
-            "location-groups" => NanoKind::LocationGroup,
-            "post-pages" => NanoKind::PostPage,
+            "child-post-posts" | "child-post-post" => NanoKind::ChildPostPost,
+            "group-users" | "group-user" => NanoKind::GroupUser,
+            "location-groups" | "location-group" => NanoKind::LocationGroup,
+            "post-pages" | "post-page" => NanoKind::PostPage,
             "project-challenges" | "project-challenge" => NanoKind::ProjectChallenge,
-            "user-badges" => NanoKind::UserBadge,
+            "user-badges" | "user-badge" => NanoKind::UserBadge,
             kind => return Err(format!("Unknown/unimplemented NanoKind: {}", kind)),
         })
     }
@@ -147,4 +148,59 @@ impl NanoKind {
             kind => panic!("Unknown/unimplemented NanoKind: {:?}", kind),
         }
     }
+
+    /// Whether a relation with the given name ties to a single object (like `user`), rather than
+    /// to many (like `project-challenges`). Returns None if the name isn't that of a known kind.
+    pub fn is_singleton_relation(name: &str) -> Option<bool> {
+        let kind = NanoKind::from_name(name).ok()?;
+
+        Some(name == kind.api_unique_name())
+    }
+
+    /// The attributes of this kind which are managed by the server, such as timestamps and
+    /// computed counts. These are stripped from request bodies when creating or updating objects,
+    /// as the server rejects writes to them.
+    pub fn read_only_attributes(&self) -> &'static [&'static str] {
+        match self {
+            NanoKind::Group => &[
+                "approved-by-id",
+                "cancelled-by-id",
+                "created-at",
+                "member-count",
+                "updated-at",
+            ],
+            NanoKind::NanoMessage => &[
+                "created-at",
+                "sender-avatar-url",
+                "sender-name",
+                "sender-slug",
+                "updated-at",
+            ],
+            NanoKind::Notification => &["created-at", "updated-at"],
+            NanoKind::Project => &["created-at"],
+            NanoKind::ProjectSession => &["created-at"],
+            NanoKind::User => &[
+                "confirmed-at",
+                "created-at",
+                "laurels",
+                "stats-projects",
+                "stats-streak",
+                "stats-word-count",
+                "stats-wordiest",
+                "stats-writing-pace",
+                "stats-years-done",
+                "stats-years-won",
+            ],
+
+            NanoKind::GroupUser => &[
+                "created-at",
+                "latest-message",
+                "num-unread-messages",
+                "updated-at",
+            ],
+            NanoKind::ProjectChallenge => &["current-count", "last-recompute", "streak", "won-at"],
+            NanoKind::UserBadge => &["created-at"],
+            _ => &[],
+        }
+    }
 }
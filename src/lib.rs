@@ -10,9 +10,13 @@ mod utils;
 pub mod client;
 pub mod data;
 pub mod error;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod sync;
 
-pub use client::NanoClient;
+pub use client::{AuthScheme, NanoClient};
 pub use data::*;
 pub use enums::*;
 pub use error::Error;
 pub use kind::NanoKind;
+#[cfg(not(target_arch = "wasm32"))]
+pub use sync::WordCountSync;
@@ -0,0 +1,113 @@
+//! Helpers to keep the Nano site up to date with a document being written elsewhere, such as in
+//! an editor
+
+use crate::{Error, NanoClient};
+
+use std::time::{Duration, Instant};
+
+#[cfg(test)]
+mod tests;
+
+/// Keeps the word count of a project challenge in sync with the total word count of a document,
+/// by adding project sessions with the difference since the last sync.
+///
+/// Totals can be reported as often as wanted, for example on every keystroke: a session is only
+/// added when the count changed by at least [`Self::with_min_delta`] words, and no more often than
+/// every [`Self::with_min_interval`]. Call [`Self::flush`] when done, such as when the document is
+/// closed, to send any remaining difference.
+///
+/// Not available on wasm32, where the standard clock can't be read.
+#[derive(Debug)]
+pub struct WordCountSync {
+    client: NanoClient,
+    project_id: u64,
+    project_challenge_id: u64,
+    min_delta: u64,
+    min_interval: Duration,
+    last_total: Option<u64>,
+    last_sent_at: Option<Instant>,
+}
+
+impl WordCountSync {
+    /// Start syncing a document with a project challenge. The first total reported is taken as
+    /// the starting point, unless one is set with [`Self::with_baseline`].
+    pub fn new(client: NanoClient, project_id: u64, project_challenge_id: u64) -> WordCountSync {
+        WordCountSync {
+            client,
+            project_id,
+            project_challenge_id,
+            min_delta: 10,
+            min_interval: Duration::from_secs(60),
+            last_total: None,
+            last_sent_at: None,
+        }
+    }
+
+    /// Set the total the document had when it was last synced, so that words written since are
+    /// counted from the first update
+    pub fn with_baseline(mut self, total: u64) -> WordCountSync {
+        self.last_total = Some(total);
+        self
+    }
+
+    /// Set the smallest change in words which is worth a session. The default is 10.
+    pub fn with_min_delta(mut self, words: u64) -> WordCountSync {
+        self.min_delta = words;
+        self
+    }
+
+    /// Set the shortest time between two sessions. The default is a minute.
+    pub fn with_min_interval(mut self, interval: Duration) -> WordCountSync {
+        self.min_interval = interval;
+        self
+    }
+
+    /// The difference to send for a new total, if it should be sent now
+    fn pending_delta(&self, total: u64, now: Instant, force: bool) -> Option<i64> {
+        let delta = total as i64 - self.last_total? as i64;
+        if delta == 0 {
+            return None;
+        }
+
+        if force {
+            return Some(delta);
+        }
+
+        let due = self
+            .last_sent_at
+            .is_none_or(|at| now.duration_since(at) >= self.min_interval);
+
+        (due && delta.unsigned_abs() >= self.min_delta).then_some(delta)
+    }
+
+    async fn sync(&mut self, total: u64, force: bool) -> Result<bool, Error> {
+        if self.last_total.is_none() {
+            self.last_total = Some(total);
+            return Ok(false);
+        }
+
+        let now = Instant::now();
+        let Some(delta) = self.pending_delta(total, now, force) else {
+            return Ok(false);
+        };
+
+        self.client
+            .add_project_session(self.project_id, self.project_challenge_id, delta)
+            .await?;
+
+        self.last_total = Some(total);
+        self.last_sent_at = Some(now);
+        Ok(true)
+    }
+
+    /// Report the current total word count of the document. Returns whether a session was added.
+    pub async fn update(&mut self, total: u64) -> Result<bool, Error> {
+        self.sync(total, false).await
+    }
+
+    /// Report the current total word count of the document, and add a session with any
+    /// difference since the last sync right away. Returns whether a session was added.
+    pub async fn flush(&mut self, total: u64) -> Result<bool, Error> {
+        self.sync(total, true).await
+    }
+}
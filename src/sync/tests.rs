@@ -0,0 +1,25 @@
+use super::*;
+
+#[test]
+fn test_pending_delta() {
+    let now = Instant::now();
+    let mut sync = WordCountSync::new(NanoClient::new_anon(), 1, 2)
+        .with_min_delta(10)
+        .with_min_interval(Duration::from_secs(60));
+
+    assert_eq!(sync.pending_delta(500, now, true), None);
+
+    sync.last_total = Some(500);
+    assert_eq!(sync.pending_delta(505, now, false), None);
+    assert_eq!(sync.pending_delta(505, now, true), Some(5));
+    assert_eq!(sync.pending_delta(520, now, false), Some(20));
+    assert_eq!(sync.pending_delta(480, now, false), Some(-20));
+    assert_eq!(sync.pending_delta(500, now, true), None);
+
+    sync.last_sent_at = Some(now);
+    assert_eq!(sync.pending_delta(520, now, false), None);
+    assert_eq!(
+        sync.pending_delta(520, now + Duration::from_secs(60), false),
+        Some(20)
+    );
+}
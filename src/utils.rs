@@ -1,10 +1,14 @@
-use crate::{NanoKind, ObjectRef, RelationLink};
+use crate::{NanoKind, ObjectRef, OneOrMany, RelationInfo, RelationLink};
 
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::fmt;
+use std::marker::PhantomData;
 use std::str::FromStr;
 
-use chrono::Duration;
+use chrono::{DateTime, Duration, NaiveDate, NaiveDateTime};
+use serde::de::value::{MapAccessDeserializer, SeqAccessDeserializer};
+use serde::de::{MapAccess, SeqAccess, Visitor};
+use serde::ser::SerializeMap;
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
 
 // TODO: Once serde supports better custom Option with annotations, use those instead
@@ -30,6 +34,115 @@ where
     Ok(de_str_num(des).ok())
 }
 
+// Not derived as untagged, so that errors inside the item(s) keep their path
+impl<'de, T: Deserialize<'de>> Deserialize<'de> for OneOrMany<T> {
+    fn deserialize<D>(des: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        struct OneOrManyVisitor<T>(PhantomData<T>);
+
+        impl<'de, T: Deserialize<'de>> Visitor<'de> for OneOrManyVisitor<T> {
+            type Value = OneOrMany<T>;
+
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                f.write_str("an object or an array of objects")
+            }
+
+            fn visit_seq<A: SeqAccess<'de>>(self, seq: A) -> Result<Self::Value, A::Error> {
+                Vec::deserialize(SeqAccessDeserializer::new(seq)).map(OneOrMany::Many)
+            }
+
+            fn visit_map<A: MapAccess<'de>>(self, map: A) -> Result<Self::Value, A::Error> {
+                T::deserialize(MapAccessDeserializer::new(map)).map(OneOrMany::One)
+            }
+        }
+
+        des.deserialize_any(OneOrManyVisitor(PhantomData))
+    }
+}
+
+pub(crate) fn de_one_or_many<'de, T, D>(des: D) -> Result<Vec<T>, D::Error>
+where
+    D: Deserializer<'de>,
+    T: Deserialize<'de>,
+{
+    OneOrMany::deserialize(des).map(OneOrMany::into_vec)
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum BoolOrInt {
+    Bool(bool),
+    Int(i64),
+}
+
+impl From<BoolOrInt> for bool {
+    fn from(val: BoolOrInt) -> bool {
+        match val {
+            BoolOrInt::Bool(val) => val,
+            BoolOrInt::Int(val) => val != 0,
+        }
+    }
+}
+
+/// Deserialize a flag which the API sends either as a boolean or as an integer, where any
+/// non-zero integer is true
+pub(crate) fn de_bool_or_int<'de, D>(des: D) -> Result<bool, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    BoolOrInt::deserialize(des).map(bool::from)
+}
+
+pub(crate) fn de_opt_bool_or_int<'de, D>(des: D) -> Result<Option<bool>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    Option::<BoolOrInt>::deserialize(des).map(|val| val.map(bool::from))
+}
+
+/// Deserialize a value which may be null, falling back to its default in that case
+pub(crate) fn de_null_default<'de, T, D>(des: D) -> Result<T, D::Error>
+where
+    T: Deserialize<'de> + Default,
+    D: Deserializer<'de>,
+{
+    Option::<T>::deserialize(des).map(Option::unwrap_or_default)
+}
+
+/// Parse a date which may be written as a plain date, or as a full timestamp whose date (as
+/// written, in its own timezone) is kept
+fn parse_flexible_date(val: &str) -> Result<NaiveDate, chrono::ParseError> {
+    NaiveDate::from_str(val)
+        .or_else(|_| DateTime::parse_from_rfc3339(val).map(|dt| dt.date_naive()))
+        .or_else(|_| NaiveDateTime::from_str(val).map(|dt| dt.date()))
+}
+
+/// Deserialize an optional date which the API sends either as a plain date or as a timestamp
+pub(crate) fn de_opt_flexible_date<'de, D>(des: D) -> Result<Option<NaiveDate>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    Option::<String>::deserialize(des)?
+        .map(|val| parse_flexible_date(&val).map_err(serde::de::Error::custom))
+        .transpose()
+}
+
+pub(crate) fn se_bool_int<S>(val: &bool, ser: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    u8::from(*val).serialize(ser)
+}
+
+pub(crate) fn se_opt_bool_int<S>(val: &Option<bool>, ser: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    val.map(u8::from).serialize(ser)
+}
+
 pub(crate) fn se_str_id<S>(num: &u64, ser: S) -> Result<S::Ok, S::Error>
 where
     S: Serializer,
@@ -97,110 +210,337 @@ where
 //     }
 // }
 
-pub(crate) fn de_rel_includes<'de, D>(des: D) -> Result<HashMap<NanoKind, Vec<ObjectRef>>, D::Error>
+/// A single relationship of an object, as sent by the API: links to fetch the related objects,
+/// and/or references to them. Null references are read as none at all.
+#[derive(Serialize, Deserialize, Debug)]
+struct RelationEntry {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    links: Option<RelationLink>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    data: Option<OneOrMany<ObjectRef>>,
+}
+
+// Not derived with two flattened maps, so that a relation with both links and references comes
+// out as a single entry, and to-many relations with a single reference keep their array
+impl<'de> Deserialize<'de> for RelationInfo {
+    fn deserialize<D>(des: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let mut info = RelationInfo {
+            included: HashMap::new(),
+            relations: HashMap::new(),
+        };
+
+        for (name, entry) in HashMap::<String, RelationEntry>::deserialize(des)? {
+            let kind = NanoKind::from_name(&name).map_err(serde::de::Error::custom)?;
+
+            if let Some(links) = entry.links {
+                info.relations.insert(kind, links);
+            }
+
+            if let Some(data) = entry.data {
+                info.included.insert(kind, data.into_vec());
+            }
+        }
+
+        Ok(info)
+    }
+}
+
+impl Serialize for RelationInfo {
+    fn serialize<S>(&self, ser: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let kinds = self
+            .relations
+            .keys()
+            .chain(self.included.keys())
+            .collect::<HashSet<_>>();
+
+        let mut map = ser.serialize_map(Some(kinds.len()))?;
+        for kind in kinds {
+            let links = self.relations.get(kind);
+            let data = self.included.get(kind);
+
+            // Without a link to tell, a single reference is taken to be a to-one relation
+            let single = links
+                .and_then(RelationLink::is_singleton)
+                .unwrap_or_else(|| data.is_some_and(|data| data.len() == 1));
+
+            let entry = RelationEntry {
+                links: links.cloned(),
+                data: data.map(|data| match data.as_slice() {
+                    [item] if single => OneOrMany::One(item.clone()),
+                    _ => OneOrMany::Many(data.clone()),
+                }),
+            };
+
+            let name = if single {
+                kind.api_unique_name()
+            } else {
+                kind.api_name()
+            };
+
+            map.serialize_entry(name, &entry)?;
+        }
+
+        map.end()
+    }
+}
+
+pub(crate) fn de_heighten_img<'de, D>(des: D) -> Result<String, D::Error>
 where
     D: Deserializer<'de>,
 {
     #[derive(Deserialize, Debug)]
-    struct DataWrap {
-        data: Option<Vec<ObjectRef>>,
+    struct ImageWrap {
+        src: String,
     }
 
-    HashMap::<String, DataWrap>::deserialize(des)
-        .map(|table| {
-            table
-                .into_iter()
-                .filter(|(_, val)| val.data.is_some())
-                .map(|(key, val)| {
-                    (
-                        NanoKind::from_name(&key).expect("unwrap de_rel_includes key"),
-                        val.data.expect("unwrap de_rel_includes val"),
-                    )
-                })
-                .collect()
-        })
-        .map_err(serde::de::Error::custom)
+    ImageWrap::deserialize(des).map(|val| val.src)
 }
 
-#[derive(Serialize, Debug)]
+#[derive(Deserialize)]
 #[serde(untagged)]
-enum SeRelIncludeInner {
-    Single { data: ObjectRef },
-    Multi(Vec<ObjectRef>),
+enum ImageUrl {
+    Url(String),
+    Image { src: String },
 }
 
-pub(crate) fn se_rel_includes<S>(
-    val: &HashMap<NanoKind, Vec<ObjectRef>>,
-    ser: S,
-) -> Result<S::Ok, S::Error>
+/// Deserialize an optional image, which the API sends either as its URL or as an object with the
+/// URL in `src`, into its URL
+pub(crate) fn de_opt_image_url<'de, D>(des: D) -> Result<Option<String>, D::Error>
 where
-    S: Serializer,
+    D: Deserializer<'de>,
 {
-    val.iter()
-        .map(|(key, val)| {
-            if val.len() == 1 {
-                (
-                    key.api_unique_name().to_string(),
-                    SeRelIncludeInner::Single {
-                        data: val.first().unwrap().clone(),
-                    },
-                )
-            } else {
-                (
-                    key.api_name().to_string(),
-                    SeRelIncludeInner::Multi(val.clone()),
-                )
-            }
-        })
-        .collect::<HashMap<String, SeRelIncludeInner>>()
-        .serialize(ser)
+    Ok(
+        Option::<ImageUrl>::deserialize(des)?.map(|image| match image {
+            ImageUrl::Url(url) | ImageUrl::Image { src: url } => url,
+        }),
+    )
 }
 
-pub(crate) fn de_relation<'de, D>(des: D) -> Result<HashMap<NanoKind, RelationLink>, D::Error>
+/// Remove the attributes of the object in a request body for which `strip` returns true, given
+/// the kind of the object if it's known, and the name and value of the attribute
+fn strip_attributes<F>(body: &mut serde_json::Value, strip: F)
 where
-    D: Deserializer<'de>,
+    F: Fn(Option<NanoKind>, &str, &serde_json::Value) -> bool,
 {
-    #[derive(Deserialize, Debug)]
-    struct LinkWrap {
-        links: RelationLink,
+    let Some(data) = body.get_mut("data").and_then(|data| data.as_object_mut()) else {
+        return;
+    };
+
+    let kind = data
+        .get("type")
+        .and_then(|kind| kind.as_str())
+        .and_then(|kind| NanoKind::from_name(kind).ok());
+
+    if let Some(attributes) = data
+        .get_mut("attributes")
+        .and_then(|attrs| attrs.as_object_mut())
+    {
+        attributes.retain(|key, val| !strip(kind, key, val));
     }
+}
 
-    HashMap::<String, LinkWrap>::deserialize(des)
-        .map(|table| {
-            table
-                .into_iter()
-                .map(|(key, val)| {
-                    (
-                        NanoKind::from_name(&key).expect("unwrap de_relation name"),
-                        val.links,
-                    )
-                })
-                .collect()
-        })
-        .map_err(serde::de::Error::custom)
+/// Whether an attribute of an object of the given kind is managed by the server
+fn is_read_only(kind: Option<NanoKind>, key: &str) -> bool {
+    kind.is_some_and(|kind| kind.read_only_attributes().contains(&key))
 }
 
-pub(crate) fn se_relation<S>(
-    val: &HashMap<NanoKind, RelationLink>,
-    ser: S,
-) -> Result<S::Ok, S::Error>
+/// Serialize a request body for a create call, stripping the attributes of the object which are
+/// managed by the server (see [`NanoKind::read_only_attributes`]), and those which are null:
+/// absent values are left out rather than sent as null, which the server rejects for some
+/// attributes
+pub(crate) fn write_view<T>(body: &T) -> Result<serde_json::Value, serde_json::Error>
 where
-    S: Serializer,
+    T: Serialize + ?Sized,
 {
-    val.iter()
-        .map(|(key, val)| (key.api_name().to_string(), val.clone()))
-        .collect::<HashMap<String, RelationLink>>()
-        .serialize(ser)
+    let mut val = serde_json::to_value(body)?;
+    strip_attributes(&mut val, |kind, key, attr| {
+        attr.is_null() || is_read_only(kind, key)
+    });
+
+    Ok(val)
 }
 
-pub(crate) fn de_heighten_img<'de, D>(des: D) -> Result<String, D::Error>
+/// Serialize a request body for an update call, stripping the attributes of the object which
+/// are managed by the server. Unlike [`write_view`], nulls are kept, as they clear the attribute.
+pub(crate) fn update_view<T>(body: &T) -> Result<serde_json::Value, serde_json::Error>
 where
-    D: Deserializer<'de>,
+    T: Serialize + ?Sized,
 {
-    #[derive(Deserialize, Debug)]
-    struct ImageWrap {
-        src: String,
+    let mut val = serde_json::to_value(body)?;
+    strip_attributes(&mut val, |kind, key, _| is_read_only(kind, key));
+
+    Ok(val)
+}
+
+/// Turn HTML markup into plain text: tags are removed, with block-level ones turned into line
+/// breaks, common entities are decoded, and whitespace is collapsed
+pub(crate) fn strip_markup(html: &str) -> String {
+    const BLOCK_TAGS: &[&str] = &[
+        "br",
+        "p",
+        "div",
+        "li",
+        "ul",
+        "ol",
+        "h1",
+        "h2",
+        "h3",
+        "h4",
+        "h5",
+        "h6",
+        "blockquote",
+        "tr",
+        "hr",
+    ];
+
+    let mut text = String::with_capacity(html.len());
+    let mut rest = html;
+    while let Some(start) = rest.find(['<', '&']) {
+        text.push_str(&rest[..start]);
+        rest = &rest[start..];
+
+        if rest.starts_with('<') {
+            let Some(end) = rest.find('>') else {
+                rest = "";
+                break;
+            };
+
+            let name = rest[1..end]
+                .trim_start_matches('/')
+                .split(|c: char| c.is_whitespace() || c == '/')
+                .next()
+                .unwrap_or_default()
+                .to_ascii_lowercase();
+            if BLOCK_TAGS.contains(&name.as_str()) {
+                text.push('\n');
+            }
+
+            rest = &rest[end + 1..];
+        } else {
+            let entity = rest[1..]
+                .find(';')
+                .filter(|&end| end <= 8)
+                .map(|end| &rest[1..end + 1]);
+
+            let decoded = entity.and_then(|entity| match entity {
+                "amp" => Some('&'),
+                "lt" => Some('<'),
+                "gt" => Some('>'),
+                "quot" => Some('"'),
+                "apos" => Some('\''),
+                "nbsp" => Some(' '),
+                _ => entity
+                    .strip_prefix("#x")
+                    .or_else(|| entity.strip_prefix("#X"))
+                    .map(|hex| u32::from_str_radix(hex, 16))
+                    .or_else(|| entity.strip_prefix('#').map(|dec| dec.parse()))
+                    .and_then(Result::ok)
+                    .and_then(char::from_u32),
+            });
+
+            match (entity, decoded) {
+                (Some(entity), Some(decoded)) => {
+                    text.push(decoded);
+                    rest = &rest[entity.len() + 2..];
+                }
+                _ => {
+                    text.push('&');
+                    rest = &rest[1..];
+                }
+            }
+        }
     }
+    text.push_str(rest);
 
-    ImageWrap::deserialize(des).map(|val| val.src)
+    let mut out = String::with_capacity(text.len());
+    let mut blank_lines = 0;
+    for line in text.lines() {
+        let line = line.split_whitespace().collect::<Vec<_>>().join(" ");
+        if line.is_empty() {
+            blank_lines += 1;
+            continue;
+        }
+
+        if !out.is_empty() {
+            out.push_str(if blank_lines > 0 { "\n\n" } else { "\n" });
+        }
+        out.push_str(&line);
+        blank_lines = 0;
+    }
+
+    out
+}
+
+/// Parse a link entered by a user, accepting only http and https URLs
+#[cfg(feature = "url")]
+pub(crate) fn parse_web_url(val: &str) -> Option<url::Url> {
+    url::Url::parse(val.trim())
+        .ok()
+        .filter(|url| matches!(url.scheme(), "http" | "https"))
+}
+
+/// Split an HTML document at its `<h2>` and `<h3>` headings, into the text of each heading
+/// along with the markup following it, up to the next heading. Anything before the first
+/// heading is left out.
+pub(crate) fn split_headings(html: &str) -> Vec<(String, &str)> {
+    let lower = html.to_ascii_lowercase();
+    let starts = lower
+        .match_indices("<h")
+        .map(|(start, _)| start)
+        .filter(|&start| {
+            matches!(
+                &lower[start + 2..].get(..2),
+                Some("2>" | "3>" | "2 " | "3 ")
+            )
+        })
+        .collect::<Vec<_>>();
+
+    starts
+        .iter()
+        .enumerate()
+        .filter_map(|(i, &start)| {
+            let section_end = starts.get(i + 1).copied().unwrap_or(html.len());
+            let level = &lower[start + 2..start + 3];
+            let close = format!("</h{level}>");
+
+            let heading_end = start + lower[start..section_end].find(&close)?;
+            let title = strip_markup(&html[start..heading_end]);
+            Some((title, &html[heading_end + close.len()..section_end]))
+        })
+        .collect()
+}
+
+/// A single item response, with the attributes of the item kept as raw JSON so that they can be
+/// decoded one at a time, without building the whole object
+#[cfg(feature = "raw_value")]
+#[derive(Deserialize, Debug)]
+pub(crate) struct RawAttributes {
+    data: RawAttributesData,
+}
+
+#[cfg(feature = "raw_value")]
+#[derive(Deserialize, Debug)]
+struct RawAttributesData {
+    attributes: HashMap<String, Box<serde_json::value::RawValue>>,
+}
+
+#[cfg(feature = "raw_value")]
+impl RawAttributes {
+    /// Decode a single attribute, by its name in the API (like `current-count`)
+    pub(crate) fn get<T>(&self, name: &str) -> Result<T, serde_json::Error>
+    where
+        T: serde::de::DeserializeOwned,
+    {
+        let raw = self.data.attributes.get(name).ok_or_else(|| {
+            <serde_json::Error as serde::de::Error>::custom(format!("missing attribute `{name}`"))
+        })?;
+
+        serde_json::from_str(raw.get())
+    }
 }